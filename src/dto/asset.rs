@@ -1,6 +1,7 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SubtitleData {
     pub page_num: i32,
     pub sentence_num: i32,
@@ -20,11 +21,68 @@ pub struct CreateAssetRequest {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateAssetResponse {
-    pub success: bool,
-    pub asset_id: Option<String>,
+    pub asset_id: String,
     pub message: String,
     pub cover_image_url: Option<String>,
     pub video_url: Option<String>,
+    pub created_by: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<UploadedFileSummary>,
 }
 
+/// One uploaded file as reported back to the dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadedFileSummary {
+    pub filename: String,
+    pub size: u64,
+    pub url: String,
+}
 
+/// Partial update for `PUT /api/assets/:id`; omitted fields are left unchanged
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateAssetRequest {
+    pub curriculum: Option<String>,
+    pub month: Option<String>,
+    pub covers: Option<Vec<String>>,
+    /// Index into `covers` (the new list when `covers` is also given)
+    pub primary_cover: Option<usize>,
+    pub subtitles: Option<Vec<SubtitleData>>,
+    pub youtube_links: Option<Vec<crate::models::asset::YouTubeLink>>,
+}
+
+/// Query filters for `GET /api/assets`
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FilterParams {
+    pub book_id: Option<String>,
+    /// `true`: assets with at least one YouTube link, `false`: assets with none
+    pub has_video: Option<bool>,
+    /// Inclusive `created_at` bounds; RFC 3339 timestamps or `YYYY-MM-DD` (midnight UTC)
+    #[serde(default, deserialize_with = "deserialize_iso8601")]
+    pub created_after: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "deserialize_iso8601")]
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+fn deserialize_iso8601<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let Some(value) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(&value) {
+        return Ok(Some(timestamp.with_timezone(&Utc)));
+    }
+    NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+        .map(|date| Some(date.and_time(NaiveTime::MIN).and_utc()))
+        .map_err(|_| serde::de::Error::custom(format!("invalid ISO 8601 date '{}'", value)))
+}
+
+impl FilterParams {
+    pub fn for_book(book_id: &str) -> Self {
+        Self {
+            book_id: Some(book_id.to_string()),
+            ..Self::default()
+        }
+    }
+}
@@ -31,6 +31,17 @@ pub struct LoginResponse {
 pub struct ClaimsResponse {
     pub username: String,
     pub role: String,
+    // Tokens issued before these claims existed decode with 0
+    #[serde(default)]
+    pub account_id: u32,
+    #[serde(default)]
+    pub agency_id: u32,
+    #[serde(default)]
+    pub academy_id: u32,
     pub exp: usize,
     pub iat: usize,
+    /// Token id used for revocation; tokens issued before it existed decode with an
+    /// empty id and are rejected by `AuthService::validate_token`
+    #[serde(default)]
+    pub jti: String,
 }
@@ -41,6 +41,7 @@ pub struct FileInfo {
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DeleteFileRequest {
+    #[serde(default)]
     pub bucket: String,
     pub key: String,
 }
@@ -1,3 +1,4 @@
 pub mod auth;
 pub mod file;
-pub mod asset;
\ No newline at end of file
+pub mod asset;
+pub mod upload;
\ No newline at end of file
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// Body of `POST /api/uploads`; the total size comes from the `Upload-Length` header
+#[derive(Debug, Deserialize)]
+pub struct CreateUploadRequest {
+    /// Key of the finished file, e.g. `R1B/Title/Title.mp4`
+    pub fullpath: String,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub bucket: Option<String>,
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UploadSessionResponse {
+    pub id: String,
+    pub file_path: String,
+    pub offset: u64,
+    pub length: u64,
+}
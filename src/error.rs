@@ -0,0 +1,141 @@
+use axum::{
+    extract::multipart::MultipartError,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::{Map, Value};
+
+use crate::services::asset::AssetError;
+use crate::services::file::{BucketNotAllowed, KeyExists};
+
+/// Error response shared by the JSON API handlers.
+///
+/// Serialized as `{"error": {"code": "NOT_FOUND", "message": "..."}}`; extra
+/// fields added with `with_detail` appear next to `code` and `message`.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+    details: Map<String, Value>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+            details: Map::new(),
+        }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "BAD_REQUEST", message)
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "UNAUTHORIZED", message)
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, "FORBIDDEN", message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "NOT_FOUND", message)
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::CONFLICT, "CONFLICT", message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", message)
+    }
+
+    /// Request body over the route's `DefaultBodyLimit`
+    pub fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::PAYLOAD_TOO_LARGE, "PAYLOAD_TOO_LARGE", message)
+    }
+
+    /// R2 / upload API failures
+    pub fn bad_gateway(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_GATEWAY, "BAD_GATEWAY", message)
+    }
+
+    pub fn with_detail(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.details.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let mut error = Map::new();
+        error.insert("code".to_string(), Value::from(self.code));
+        error.insert("message".to_string(), Value::from(self.message));
+        error.extend(self.details);
+
+        (self.status, Json(serde_json::json!({ "error": error }))).into_response()
+    }
+}
+
+impl From<BucketNotAllowed> for ApiError {
+    fn from(error: BucketNotAllowed) -> Self {
+        let message = error.to_string();
+        Self::new(StatusCode::BAD_REQUEST, "BUCKET_NOT_ALLOWED", message).with_detail("bucket", error.0)
+    }
+}
+
+impl From<AssetError> for ApiError {
+    fn from(error: AssetError) -> Self {
+        let message = error.to_string();
+        match error {
+            AssetError::NotFound(_) => Self::not_found(message),
+            AssetError::PrimaryCoverOutOfRange { .. } => {
+                Self::new(StatusCode::BAD_REQUEST, "INVALID_PRIMARY_COVER", message)
+            }
+            AssetError::LimitExceeded { field, max, .. } => Self::new(StatusCode::BAD_REQUEST, "LIMIT_EXCEEDED", message)
+                .with_detail("field", field)
+                .with_detail("max", max),
+        }
+    }
+}
+
+impl From<KeyExists> for ApiError {
+    fn from(error: KeyExists) -> Self {
+        Self::conflict(format!("이미 존재하는 파일입니다: {}", error.key))
+            .with_detail("existing_key", error.key)
+            .with_detail("existing_url", error.url)
+    }
+}
+
+// multipart 본문이 DefaultBodyLimit를 넘으면 413, 나머지 파싱 오류는 400
+impl From<MultipartError> for ApiError {
+    fn from(error: MultipartError) -> Self {
+        if error.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            Self::payload_too_large("Request body exceeds the upload size limit")
+        } else {
+            Self::bad_request(format!("Invalid multipart body: {}", error.body_text()))
+        }
+    }
+}
@@ -0,0 +1,483 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{rejection::QueryRejection, Extension, OriginalUri, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json},
+};
+use serde::Deserialize;
+use tracing::{error, info};
+
+use crate::{
+    dto::asset::{FilterParams, SubtitleData, UpdateAssetRequest},
+    error::ApiError,
+    models::{
+        asset::{Asset, YouTubeLink},
+        user::{actor_name, AdminUser},
+    },
+    services::{
+        asset::{cross_reference_files, diff_json_file, reorder_links, AssetDiffReport, AssetError, JsonFileDiff, JsonFileStatus, R2JsonFile},
+        subtitle::parse_subtitles,
+    },
+    utils::{json_format::JsonFormat, pagination::PageParams},
+    AppState,
+};
+
+const CSV_EXPORT_HEADER: [&str; 7] = [
+    "curriculum",
+    "month",
+    "book_id",
+    "cover_count",
+    "subtitle_count",
+    "youtube_link_count",
+    "created_at",
+];
+
+#[derive(Debug, Deserialize)]
+pub struct AssetFilesQuery {
+    pub category: Option<String>,
+}
+
+// 에셋 목록 (book_id, has_video, 생성일 범위 필터, page/limit 페이지네이션)
+pub async fn list_assets(
+    State(app_state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    format: JsonFormat,
+    filter: Result<Query<FilterParams>, QueryRejection>,
+    page_params: Result<Query<PageParams>, QueryRejection>,
+) -> impl IntoResponse {
+    let (filter, page_params) = match (filter, page_params) {
+        (Ok(Query(filter)), Ok(Query(page_params))) => (filter, page_params),
+        (Err(rejection), _) | (_, Err(rejection)) => {
+            return ApiError::bad_request(rejection.body_text()).into_response();
+        }
+    };
+
+    let assets = app_state.asset_service.get_filtered_assets(&filter).await;
+    info!("Listing {} assets (filter: {:?})", assets.len(), filter);
+    let page = page_params.paginate(assets);
+    (page.headers(&uri), format.json(&page.items)).into_response()
+}
+
+// 교재(book_id)별 에셋 메타데이터와 R2 실제 파일을 비교
+pub async fn get_asset_files(
+    State(app_state): State<AppState>,
+    Path(book_id): Path<String>,
+    Query(query): Query<AssetFilesQuery>,
+) -> impl IntoResponse {
+    let category = app_state.file_service.category_or_default(query.category);
+    info!("Cross-referencing assets for book_id: {} (category: {})", book_id, category);
+
+    let assets = app_state.asset_service.get_filtered_assets(&FilterParams::for_book(&book_id)).await;
+
+    let prefix = format!("{}/", book_id.trim_end_matches('/'));
+    let r2_files = match app_state.file_service.get_r2_folder_files_with_category(&prefix, &category).await {
+        Ok(files) => files,
+        Err(e) => {
+            error!("Failed to list R2 files for {}: {}", book_id, e);
+            return ApiError::bad_gateway(format!("Failed to list R2 files: {}", e)).into_response();
+        }
+    };
+
+    // 폴더 항목은 제외하고 실제 파일 키만 비교
+    let r2_keys: Vec<String> = r2_files
+        .into_iter()
+        .filter(|item| item.value.file.is_some())
+        .map(|item| item.key)
+        .collect();
+    let files = cross_reference_files(&assets, &r2_keys);
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "book_id": book_id,
+            "category": category,
+            "assets": assets,
+            "files": files
+        }))
+    ).into_response()
+}
+
+// 에셋 카탈로그 CSV 다운로드 (행 단위로 직렬화해서 스트리밍)
+pub async fn export_assets_csv(State(app_state): State<AppState>) -> impl IntoResponse {
+    let assets = app_state.asset_service.get_filtered_assets(&FilterParams::default()).await;
+    info!("Exporting {} assets as CSV", assets.len());
+
+    let header_row = std::iter::once(csv_row(&CSV_EXPORT_HEADER));
+    let rows = assets.into_iter().map(|asset| csv_row(&asset_csv_record(&asset)));
+    let stream = futures::stream::iter(header_row.chain(rows));
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "attachment; filename=\"assets.csv\""),
+        ],
+        Body::from_stream(stream),
+    ).into_response()
+}
+
+fn asset_csv_record(asset: &Asset) -> [String; 7] {
+    [
+        asset.curriculum.clone().unwrap_or_default(),
+        asset.month.clone().unwrap_or_default(),
+        asset.book_id.clone(),
+        asset.cover_image_count().to_string(),
+        asset.subtitles.len().to_string(),
+        asset.youtube_links.len().to_string(),
+        asset.created_at.to_rfc3339(),
+    ]
+}
+
+fn csv_row<T: AsRef<[u8]>>(record: &[T]) -> Result<Bytes, std::io::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(record)?;
+    writer
+        .into_inner()
+        .map(Bytes::from)
+        .map_err(|e| std::io::Error::other(e.to_string()))
+}
+
+// 자막 일괄 가져오기 (CSV 또는 JSON, Content-Type으로 구분) - 에셋 자막과 subtitle.json 교체
+pub async fn import_subtitles(
+    State(app_state): State<AppState>,
+    user: Option<Extension<AdminUser>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    body: String,
+) -> impl IntoResponse {
+    let Some(asset) = app_state.asset_service.get(&id).await else {
+        return ApiError::not_found(format!("Asset not found: {}", id)).into_response();
+    };
+
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+    let subtitles = match parse_subtitles(content_type, &body) {
+        Ok(subtitles) => subtitles,
+        Err(e) => {
+            info!("Rejected subtitle import for {}: {}", id, e);
+            return ApiError::bad_request(e.to_string()).into_response();
+        }
+    };
+    if let Err(e) = app_state.asset_service.check_limits(None, None, Some(subtitles.len())) {
+        info!("Rejected subtitle import for {}: {}", id, e);
+        return ApiError::from(e).into_response();
+    }
+
+    let subtitle_json = match serde_json::to_vec(&subtitles) {
+        Ok(json) => json,
+        Err(e) => {
+            error!("Failed to serialize subtitles for {}: {}", id, e);
+            return ApiError::internal(format!("Failed to serialize subtitles: {}", e)).into_response();
+        }
+    };
+    let base_path = format!("{}/{}/", asset.book_id, asset.title);
+    let files = vec![("subtitle.json".to_string(), Bytes::from(subtitle_json))];
+    if let Err(e) = app_state.file_service.upload_file(files, None, &base_path, Some(&asset.category), true).await {
+        error!("Failed to upload subtitle.json for {}: {}", id, e);
+        return ApiError::bad_gateway(format!("Failed to upload subtitle.json: {}", e)).into_response();
+    }
+
+    let count = subtitles.len();
+    let actor = actor_name(user.as_ref().map(|Extension(user)| user));
+    app_state.asset_service.replace_subtitles(&id, subtitles, &actor).await;
+    info!("Imported {} subtitles for asset {}", count, id);
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "asset_id": id,
+            "imported": count
+        }))
+    ).into_response()
+}
+
+// 에셋의 YouTube 링크 유효성 확인 (oEmbed)
+pub async fn validate_youtube_links(
+    State(app_state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match app_state.asset_service.validate_youtube_links(&app_state.youtube_service, &id).await {
+        Some(report) => {
+            info!("Validated {} YouTube links for {} ({} broken)", report.links.len(), id, report.broken.len());
+            (
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "report": report
+                }))
+            ).into_response()
+        }
+        None => ApiError::not_found(format!("Asset not found: {}", id)).into_response(),
+    }
+}
+
+/// New link order, either as indices into the current list or as `youtube_url`s
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum YouTubeLinkOrder {
+    Indices(Vec<usize>),
+    Urls(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReorderYouTubeLinksRequest {
+    pub order: YouTubeLinkOrder,
+}
+
+// YouTube 링크 순서 변경 - 기존 링크의 순열이어야 하며 youtube_links.json도 다시 씀
+pub async fn reorder_youtube_links(
+    State(app_state): State<AppState>,
+    user: Option<Extension<AdminUser>>,
+    Path(id): Path<String>,
+    Json(request): Json<ReorderYouTubeLinksRequest>,
+) -> impl IntoResponse {
+    let Some(asset) = app_state.asset_service.get(&id).await else {
+        return ApiError::not_found(format!("Asset not found: {}", id)).into_response();
+    };
+
+    let indices = match request.order {
+        YouTubeLinkOrder::Indices(indices) => Some(indices),
+        YouTubeLinkOrder::Urls(urls) => urls
+            .iter()
+            .map(|url| asset.youtube_links.iter().position(|link| &link.youtube_url == url))
+            .collect(),
+    };
+    let Some(reordered) = indices.and_then(|indices| reorder_links(&asset.youtube_links, &indices)) else {
+        return ApiError::bad_request(format!("order must be a permutation of the asset's {} YouTube links", asset.youtube_links.len())).into_response();
+    };
+
+    if let Err(response) = upload_asset_json(&app_state, &asset, "youtube_links.json", &reordered).await {
+        return response;
+    }
+
+    let actor = actor_name(user.as_ref().map(|Extension(user)| user));
+    app_state.asset_service.replace_youtube_links(&id, reordered.clone(), &actor).await;
+    info!("Reordered {} YouTube links for asset {}", reordered.len(), id);
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "asset_id": id,
+            "youtube_links": reordered
+        }))
+    ).into_response()
+}
+
+// youtube_links.json만 재생성 (파일명 기반 제목, 저장된 링크는 그대로)
+pub async fn regenerate_youtube_links(
+    State(app_state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let (Some(asset), Some(links)) = (
+        app_state.asset_service.get(&id).await,
+        app_state.asset_service.regenerate_youtube_links(&id).await,
+    ) else {
+        return ApiError::from(AssetError::NotFound(id)).into_response();
+    };
+
+    if let Err(response) = upload_asset_json(&app_state, &asset, "youtube_links.json", &links).await {
+        return response;
+    }
+    info!("Regenerated youtube_links.json ({} links) for asset {}", links.len(), id);
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "asset_id": id,
+            "youtube_links": links
+        }))
+    ).into_response()
+}
+
+// 에셋 폴더의 JSON 목록 파일(youtube_links.json, subtitle.json) 덮어쓰기
+async fn upload_asset_json<T: serde::Serialize>(
+    app_state: &AppState,
+    asset: &Asset,
+    filename: &str,
+    entries: &[T],
+) -> Result<(), axum::response::Response> {
+    let json = serde_json::to_vec(entries).map_err(|e| {
+        error!("Failed to serialize {} for {}: {}", filename, asset.id, e);
+        ApiError::internal(format!("Failed to serialize {}: {}", filename, e)).into_response()
+    })?;
+    let base_path = format!("{}/{}/", asset.book_id, asset.title);
+    let files = vec![(filename.to_string(), Bytes::from(json))];
+    if let Err(e) = app_state.file_service.upload_file(files, None, &base_path, Some(&asset.category), true).await {
+        error!("Failed to upload {} for {}: {}", filename, asset.id, e);
+        return Err(ApiError::bad_gateway(format!("Failed to upload {}: {}", filename, e)).into_response());
+    }
+    Ok(())
+}
+
+// R2의 JSON 목록 파일 읽기 (R2 오류는 502 응답)
+async fn fetch_json_file<T: serde::de::DeserializeOwned>(app_state: &AppState, path: &str) -> Result<R2JsonFile<T>, axum::response::Response> {
+    match app_state.file_service.fetch_file_bytes(path).await {
+        Ok(None) => Ok(R2JsonFile::Missing),
+        Ok(Some(bytes)) => Ok(match serde_json::from_slice(&bytes) {
+            Ok(entries) => R2JsonFile::Parsed(entries),
+            Err(e) => R2JsonFile::Unreadable(e.to_string()),
+        }),
+        Err(e) => {
+            error!("Failed to read {}: {}", path, e);
+            Err(ApiError::bad_gateway(format!("Failed to read {}: {}", path, e)).into_response())
+        }
+    }
+}
+
+// (subtitle.json, youtube_links.json) 각각을 에셋 메타데이터와 비교
+async fn diff_asset_files(app_state: &AppState, asset: &Asset) -> Result<(JsonFileDiff, JsonFileDiff), axum::response::Response> {
+    let base_path = format!("{}/{}/", asset.book_id, asset.title);
+    let subtitles = fetch_json_file::<SubtitleData>(app_state, &format!("{}subtitle.json", base_path)).await?;
+    let youtube_links = fetch_json_file::<YouTubeLink>(app_state, &format!("{}youtube_links.json", base_path)).await?;
+    Ok((
+        diff_json_file("subtitle.json", &asset.subtitles, &subtitles),
+        diff_json_file("youtube_links.json", &asset.youtube_links, &youtube_links),
+    ))
+}
+
+// 에셋 메타데이터와 R2의 subtitle.json / youtube_links.json 비교 (부분 쓰기로 어긋난 경우 탐지)
+pub async fn get_asset_diff(
+    State(app_state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Some(asset) = app_state.asset_service.get(&id).await else {
+        return ApiError::not_found(format!("Asset not found: {}", id)).into_response();
+    };
+    let (subtitles, youtube_links) = match diff_asset_files(&app_state, &asset).await {
+        Ok(diffs) => diffs,
+        Err(response) => return response,
+    };
+
+    let files = vec![subtitles, youtube_links];
+    let in_sync = files.iter().all(|file| file.status == JsonFileStatus::InSync);
+    info!("Diffed asset {} against R2 (in sync: {})", id, in_sync);
+
+    Json(AssetDiffReport { asset_id: id, in_sync, files }).into_response()
+}
+
+// 어긋난 JSON 파일을 에셋 메타데이터 기준으로 다시 씀 (일치하는 파일은 건드리지 않으므로 반복 호출해도 동일)
+pub async fn reconcile_asset(
+    State(app_state): State<AppState>,
+    user: Option<Extension<AdminUser>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let actor = actor_name(user.as_ref().map(|Extension(user)| user));
+    let Some(asset) = app_state.asset_service.get(&id).await else {
+        return ApiError::not_found(format!("Asset not found: {}", id)).into_response();
+    };
+    let (subtitles, youtube_links) = match diff_asset_files(&app_state, &asset).await {
+        Ok(diffs) => diffs,
+        Err(response) => return response,
+    };
+
+    let mut rewritten = Vec::new();
+    let mut unchanged = Vec::new();
+    let mut result = Ok(());
+    if subtitles.status == JsonFileStatus::InSync {
+        unchanged.push(subtitles.file);
+    } else {
+        result = upload_asset_json(&app_state, &asset, &subtitles.file, &asset.subtitles).await;
+        rewritten.push(subtitles.file);
+    }
+    if youtube_links.status == JsonFileStatus::InSync {
+        unchanged.push(youtube_links.file);
+    } else if result.is_ok() {
+        result = upload_asset_json(&app_state, &asset, &youtube_links.file, &asset.youtube_links).await;
+        rewritten.push(youtube_links.file);
+    }
+    app_state.audit_service.record(&actor, "reconcile_asset", &id, result.is_ok()).await;
+    if let Err(response) = result {
+        return response;
+    }
+    info!("Reconciled asset {} (rewritten: {:?}, by {})", id, rewritten, actor);
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "asset_id": id,
+            "rewritten": rewritten,
+            "unchanged": unchanged
+        }))
+    ).into_response()
+}
+
+// 에셋 메타데이터 부분 수정
+pub async fn update_asset(
+    State(app_state): State<AppState>,
+    user: Option<Extension<AdminUser>>,
+    Path(id): Path<String>,
+    Json(update): Json<UpdateAssetRequest>,
+) -> impl IntoResponse {
+    let actor = actor_name(user.as_ref().map(|Extension(user)| user));
+
+    // 자막/YouTube 링크를 바꾸면 subtitle.json, youtube_links.json을 먼저 다시 씀 (실패하면 메타데이터도 그대로)
+    if update.subtitles.is_some() || update.youtube_links.is_some() {
+        let asset = match app_state.asset_service.check_update(&id, &update).await {
+            Ok(asset) => asset,
+            Err(e) => {
+                app_state.audit_service.record(&actor, "update_asset", &id, false).await;
+                info!("Asset update rejected for {}: {}", id, e);
+                return ApiError::from(e).into_response();
+            }
+        };
+        if let Some(subtitles) = &update.subtitles {
+            if let Err(response) = upload_asset_json(&app_state, &asset, "subtitle.json", subtitles).await {
+                app_state.audit_service.record(&actor, "update_asset", &id, false).await;
+                return response;
+            }
+        }
+        if let Some(youtube_links) = &update.youtube_links {
+            if let Err(response) = upload_asset_json(&app_state, &asset, "youtube_links.json", youtube_links).await {
+                app_state.audit_service.record(&actor, "update_asset", &id, false).await;
+                return response;
+            }
+        }
+    }
+
+    let result = app_state.asset_service.update_asset(&id, update, &actor).await;
+    app_state.audit_service.record(&actor, "update_asset", &id, result.is_ok()).await;
+
+    match result {
+        Ok(asset) => {
+            info!("Asset updated: {} (by {})", id, actor);
+            (
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "asset": asset
+                }))
+            ).into_response()
+        }
+        Err(e) => {
+            info!("Asset update rejected for {}: {}", id, e);
+            ApiError::from(e).into_response()
+        }
+    }
+}
+
+// 에셋 메타데이터 삭제 (R2 파일은 그대로 둠)
+pub async fn delete_asset(
+    State(app_state): State<AppState>,
+    user: Option<Extension<AdminUser>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let actor = actor_name(user.as_ref().map(|Extension(user)| user));
+    let result = app_state.asset_service.delete_asset(&id).await;
+    app_state.audit_service.record(&actor, "delete_asset", &id, result.is_ok()).await;
+
+    match result {
+        Ok(_) => {
+            info!("Asset deleted: {} (by {})", id, actor);
+            (
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "asset_id": id
+                }))
+            ).into_response()
+        }
+        Err(e) => {
+            info!("Asset delete rejected for {}: {}", id, e);
+            ApiError::from(e).into_response()
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{utils::json_format::JsonFormat, AppState};
+
+#[derive(Debug, Deserialize)]
+pub struct AuditLogQuery {
+    #[serde(default = "default_page")]
+    pub page: usize,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+fn default_page() -> usize {
+    1
+}
+
+fn default_limit() -> usize {
+    20
+}
+
+pub async fn get_audit_logs(
+    State(app_state): State<AppState>,
+    format: JsonFormat,
+    Query(query): Query<AuditLogQuery>,
+) -> impl IntoResponse {
+    let page = query.page.max(1);
+    let limit = query.limit.clamp(1, 100);
+    info!("Getting audit logs (page: {}, limit: {})", page, limit);
+
+    let (logs, total) = app_state.audit_service.list(page, limit).await;
+
+    format.json(&serde_json::json!({
+        "logs": logs,
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": total,
+            "total_pages": total.div_ceil(limit)
+        }
+    }))
+}
@@ -1,19 +1,18 @@
 use axum::{
-    extract::State, 
-    response::{Html, Json, Redirect}, 
+    extract::{Extension, State}, 
+    response::{Html, IntoResponse, Json, Redirect}, 
     Form, 
-    http::{StatusCode, header::{SET_COOKIE, HeaderMap}},
+    http::header::{SET_COOKIE, HeaderMap},
 };
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use crate::{AppState, services::auth::AuthService, models::user::AdminUser, middleware::auth::extract_token_from_headers};
+use crate::{AppState, dto::auth::ClaimsResponse, error::ApiError, models::user::AdminUser, middleware::auth::extract_token_from_headers};
 
-pub async fn root_handler(headers: HeaderMap) -> Redirect {
+pub async fn root_handler(State(app_state): State<AppState>, headers: HeaderMap) -> Redirect {
     // Check if user has a valid token
     if let Some(token) = extract_token_from_headers(&headers) {
-        let auth_service = AuthService::new();
-        if auth_service.validate_token(&token).is_ok() {
+        if app_state.auth_service.validate_token(&token).is_ok() {
             // Token is valid, redirect to admin dashboard
             return Redirect::permanent("/dashboard");
         }
@@ -35,73 +34,67 @@ pub struct LoginForm {
 
 #[derive(Serialize)]
 pub struct LoginSuccess {
-    pub success: bool,
     pub user: AdminUser,
     pub token: String,
 }
 
-#[derive(Serialize)]
-pub struct LoginError {
-    pub success: bool,
-    pub message: String,
-}
-
 pub async fn login_handler(
-    State(_app_state): State<AppState>,
+    State(app_state): State<AppState>,
     Form(login_form): Form<LoginForm>,
-) -> Result<(HeaderMap, Json<LoginSuccess>), (StatusCode, Json<LoginError>)> {
+) -> Result<(HeaderMap, Json<LoginSuccess>), ApiError> {
     info!("Login attempt for account: {}", login_form.account);
-    let auth_service = AuthService::new();
-    
+    let auth_service = &app_state.auth_service;
+
     match auth_service.authenticate_user(&login_form.account, &login_form.password).await {
         Ok(Some(admin_user)) => {
             // Generate JWT token for the authenticated user
-            match auth_service.generate_admin_token(&admin_user) {
-                Ok(token) => {
-                    // Create cookie headers
-                    let mut headers = HeaderMap::new();
-                    // HttpOnly cookie for actual authentication
-                    let auth_cookie = format!("auth_token={}; HttpOnly; SameSite=Lax; Path=/; Max-Age=86400", token);
-                    headers.insert(SET_COOKIE, auth_cookie.parse().unwrap());
-                    
-                    // Non-HttpOnly cookie for JavaScript to check auth status
-                    let status_cookie = format!("auth_status=authenticated; SameSite=Lax; Path=/; Max-Age=86400");
-                    headers.append(SET_COOKIE, status_cookie.parse().unwrap());
-                    
-                    Ok((headers, Json(LoginSuccess {
-                        success: true,
-                        user: admin_user,
-                        token,
-                    })))
-                },
-                Err(e) => {
-                    Err((
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(LoginError {
-                            success: false,
-                            message: format!("Token generation error: {}", e),
-                        }),
-                    ))
-                }
-            }
-        },
-        Ok(None) => {
-            Err((
-                StatusCode::UNAUTHORIZED,
-                Json(LoginError {
-                    success: false,
-                    message: "Invalid credentials".to_string(),
-                }),
-            ))
+            let token = auth_service
+                .generate_admin_token(&admin_user)
+                .map_err(|e| ApiError::internal(format!("Token generation error: {}", e)))?;
+
+            // Create cookie headers
+            let mut headers = HeaderMap::new();
+            let cookie_config = &app_state.config.cookie;
+            // HttpOnly cookie for actual authentication
+            let auth_cookie = cookie_config.build_cookie("auth_token", &token, true, 86400);
+            headers.insert(SET_COOKIE, auth_cookie.parse().unwrap());
+            
+            // Non-HttpOnly cookie for JavaScript to check auth status
+            let status_cookie = cookie_config.build_cookie("auth_status", "authenticated", false, 86400);
+            headers.append(SET_COOKIE, status_cookie.parse().unwrap());
+            
+            Ok((headers, Json(LoginSuccess {
+                user: admin_user,
+                token,
+            })))
         },
-        Err(e) => {
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(LoginError {
-                    success: false,
-                    message: format!("Authentication error: {}", e),
-                }),
-            ))
-        }
+        Ok(None) => Err(ApiError::unauthorized("Invalid credentials")),
+        Err(e) => Err(ApiError::internal(format!("Authentication error: {}", e))),
     }
-}
\ No newline at end of file
+}
+
+pub async fn me_handler(Extension(admin_user): Extension<AdminUser>) -> Json<AdminUser> {
+    Json(admin_user)
+}
+
+// 현재 토큰을 폐기하고 인증 쿠키 삭제
+pub async fn logout_handler(
+    State(app_state): State<AppState>,
+    Extension(claims): Extension<ClaimsResponse>,
+) -> impl IntoResponse {
+    let revoked = app_state.auth_service.revoke_token(&claims);
+    if let Err(e) = &revoked {
+        info!("Logout for {} without revocation: {}", claims.username, e);
+    } else {
+        info!("Logged out {} (token revoked)", claims.username);
+    }
+
+    let cookie_config = &app_state.config.cookie;
+    let mut headers = HeaderMap::new();
+    headers.insert(SET_COOKIE, cookie_config.build_cookie("auth_token", "", true, 0).parse().unwrap());
+    headers.append(SET_COOKIE, cookie_config.build_cookie("auth_status", "", false, 0).parse().unwrap());
+
+    (headers, Json(serde_json::json!({
+        "revoked": revoked.is_ok()
+    })))
+}
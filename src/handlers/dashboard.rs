@@ -1,13 +1,35 @@
 use axum::{
-    extract::{Multipart, State, Path, Query},
-    http::{StatusCode, HeaderMap, HeaderValue},
-    response::{Html, IntoResponse, Json},
+    extract::{Extension, Multipart, OriginalUri, State, Path, Query},
+    http::{StatusCode, HeaderMap, HeaderValue, Uri},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Json, Response,
+    },
 };
+use futures::{Stream, StreamExt};
+use std::{collections::{BTreeMap, HashSet}, convert::Infallible};
+use tokio::sync::broadcast::error::RecvError;
 use tracing::{error, info};
 use crate::{
-    dto::asset::{CreateAssetResponse, SubtitleData},
+    dto::asset::{CreateAssetResponse, SubtitleData, UploadedFileSummary},
+    error::ApiError,
+    models::asset::{Asset, YouTubeLink},
+    services::file::{FileService, KeyExists, R2WorkerFileItem},
+    services::idempotency::Reservation,
+    services::project_list::{find_stage_and_month, read_project_list, ProjectListCache, ProjectListError, PROJECT_LIST_PATH},
+    models::user::{actor_name, AdminUser},
+    utils::{
+        etag::{self, json_with_etag},
+        json_format::JsonFormat,
+        media,
+        pagination::PageParams,
+        multipart::{is_blank_file_name, MISSING_FULLPATH_MESSAGE, NO_FILE_MESSAGE},
+        path::{build_breadcrumbs, is_single_segment, normalize_path},
+        spool::{SpoolError, SpooledFile},
+    },
     AppState,
 };
+pub use crate::utils::path::BreadcrumbItem;
 use serde::{Deserialize, Serialize};
 
 pub async fn dashboard_main() -> Html<&'static str> {
@@ -37,121 +59,455 @@ pub struct FolderContentsResponse {
     pub breadcrumbs: Vec<BreadcrumbItem>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BreadcrumbItem {
-    pub name: String,
-    pub path: String,
+#[derive(Deserialize)]
+pub struct CategoryQuery {
+    pub category: Option<String>, // 없으면 설정된 기본 카테고리
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    #[default]
+    Name,
+    Size,
+    Modified,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
 }
 
 #[derive(Deserialize)]
-pub struct CategoryQuery {
-    #[serde(default = "default_category")]
-    pub category: String,
+pub struct FolderListingQuery {
+    pub category: Option<String>, // 없으면 설정된 기본 카테고리
+    #[serde(default)]
+    pub sort_by: SortBy,
+    #[serde(default)]
+    pub order: SortOrder,
+    pub file_type: Option<String>, // "image", "video", "pdf", "text", "other"
+    #[serde(default)]
+    pub fresh: bool, // true면 캐시를 건너뛰고 R2에서 다시 조회
+    pub q: Option<String>, // /api/folder-search 요청의 파일명 검색어
 }
 
-fn default_category() -> String {
-    "reengkigo".to_string()
+// fresh=true 요청 시 캐시 강제 갱신 (실패하거나 제한되면 기존 캐시 사용)
+async fn refresh_if_requested(app_state: &AppState, fresh: bool, category: &str) {
+    if !fresh {
+        return;
+    }
+    if let Err(e) = app_state.file_service.force_refresh_with_category(category).await {
+        error!("Forced refresh failed for category {}, using cached data: {}", category, e);
+    }
+}
+
+/// Filters files by `file_type` and sorts the listing.
+///
+/// Folders are always kept and listed before files; the sort key and order
+/// apply within each group.
+pub fn sort_and_filter_items(items: &mut Vec<FolderItem>, sort_by: SortBy, order: SortOrder, file_type: Option<&str>) {
+    if let Some(file_type) = file_type {
+        items.retain(|item| item.item_type == "folder" || item.file_type.as_deref() == Some(file_type));
+    }
+
+    items.sort_by(|a, b| {
+        match (a.item_type.as_str(), b.item_type.as_str()) {
+            ("folder", "file") => return std::cmp::Ordering::Less,
+            ("file", "folder") => return std::cmp::Ordering::Greater,
+            _ => {}
+        }
+
+        let ordering = match sort_by {
+            SortBy::Name => a.name.cmp(&b.name),
+            SortBy::Size => a.size.cmp(&b.size).then_with(|| a.name.cmp(&b.name)),
+            SortBy::Modified => a.modified_at.cmp(&b.modified_at).then_with(|| a.name.cmp(&b.name)),
+        };
+
+        match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    });
 }
 
 pub async fn get_folder_contents(
     State(app_state): State<AppState>,
     Path(folder_path): Path<String>,
-    Query(query): Query<CategoryQuery>
+    Query(query): Query<FolderListingQuery>,
+    Query(page_params): Query<PageParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
-    info!("Getting folder contents for path: {} with category: {}", folder_path, query.category);
+    let category = app_state.file_service.category_or_default(query.category);
+    refresh_if_requested(&app_state, query.fresh, &category).await;
+
+    info!("Getting folder contents for path: {} with category: {}", folder_path, category);
     
-    match build_folder_structure_with_category(&app_state, &folder_path, &query.category).await {
-        Ok(response) => {
-            (StatusCode::OK, Json(response)).into_response()
+    match build_folder_structure_with_category(&app_state, &folder_path, &category).await {
+        Ok(mut response) => {
+            sort_and_filter_items(&mut response.items, query.sort_by, query.order, query.file_type.as_deref());
+            let listing = paginated_listing(&headers, &uri, response, page_params);
+            with_stale_warning(&app_state, &category, listing).await
         }
         Err(error) => {
             error!("Failed to get folder contents: {}", error);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "error": format!("Failed to get folder contents: {}", error)
-                }))
-            ).into_response()
+            ApiError::internal(format!("Failed to get folder contents: {}", error)).into_response()
         }
     }
 }
 
 pub async fn get_root_folders(
     State(app_state): State<AppState>,
-    Query(query): Query<CategoryQuery>
+    Query(query): Query<FolderListingQuery>,
+    Query(page_params): Query<PageParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
-    info!("Getting root folders with category: {}", query.category);
+    let category = app_state.file_service.category_or_default(query.category);
+    info!("Getting root folders with category: {}", category);
+    refresh_if_requested(&app_state, query.fresh, &category).await;
     
-    match build_folder_structure_with_category(&app_state, "", &query.category).await {
-        Ok(response) => {
-            (StatusCode::OK, Json(response)).into_response()
+    match build_folder_structure_with_category(&app_state, "", &category).await {
+        Ok(mut response) => {
+            sort_and_filter_items(&mut response.items, query.sort_by, query.order, query.file_type.as_deref());
+            let listing = paginated_listing(&headers, &uri, response, page_params);
+            with_stale_warning(&app_state, &category, listing).await
         }
         Err(error) => {
             error!("Failed to get root folders: {}", error);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "error": format!("Failed to get root folders: {}", error)
-                }))
-            ).into_response()
+            ApiError::internal(format!("Failed to get root folders: {}", error)).into_response()
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FileTypeSummary {
+    pub count: usize,
+    pub bytes: u64,
+}
+
+/// File counts and sizes under a folder (all depths), grouped by `get_file_type`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FolderSummaryResponse {
+    pub path: String,
+    pub total_files: usize,
+    pub total_bytes: u64,
+    pub by_type: BTreeMap<String, FileTypeSummary>,
+}
+
+/// Files under a folder (all depths) whose name contains the search query
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FolderSearchResponse {
+    pub path: String,
+    pub query: String,
+    pub items: Vec<FolderItem>,
+}
+
+pub async fn get_root_folder_summary(
+    State(app_state): State<AppState>,
+    Query(query): Query<FolderListingQuery>,
+) -> Response {
+    let category = app_state.file_service.category_or_default(query.category);
+    refresh_if_requested(&app_state, query.fresh, &category).await;
+    folder_summary(&app_state, "", &category).await
+}
+
+pub async fn get_folder_summary(
+    State(app_state): State<AppState>,
+    Path(folder_path): Path<String>,
+    Query(query): Query<FolderListingQuery>,
+) -> Response {
+    let category = app_state.file_service.category_or_default(query.category);
+    refresh_if_requested(&app_state, query.fresh, &category).await;
+    folder_summary(&app_state, &folder_path, &category).await
+}
+
+async fn folder_summary(app_state: &AppState, folder_path: &str, category: &str) -> Response {
+    let path = normalize_path(folder_path);
+    info!("Summarizing folder: '{}' with category: {}", path, category);
+
+    let (key, prefix) = if path.is_empty() {
+        ("*".to_string(), String::new())
+    } else {
+        (format!("{}/", path), format!("{}/", path))
+    };
+    let files = match app_state.file_service.get_r2_folder_files_with_category(&key, category).await {
+        Ok(files) => files,
+        Err(e) => {
+            error!("Failed to summarize folder {}: {}", path, e);
+            return ApiError::internal(format!("Failed to summarize folder: {}", e)).into_response();
         }
+    };
+
+    let mut by_type: BTreeMap<String, FileTypeSummary> = BTreeMap::new();
+    for item in &files {
+        let Some(relative) = item.key.strip_prefix(&prefix) else { continue };
+        // 폴더 표시용 키("a/")는 제외
+        if relative.is_empty() || relative.ends_with('/') {
+            continue;
+        }
+        let summary = by_type.entry(get_file_type(relative)).or_default();
+        summary.count += 1;
+        summary.bytes += item.value.size;
+    }
+
+    Json(FolderSummaryResponse {
+        path,
+        total_files: by_type.values().map(|summary| summary.count).sum(),
+        total_bytes: by_type.values().map(|summary| summary.bytes).sum(),
+        by_type,
+    }).into_response()
+}
+
+pub async fn get_root_folder_search(
+    State(app_state): State<AppState>,
+    Query(query): Query<FolderListingQuery>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> Response {
+    search_response(&app_state, "", query, &uri, &headers).await
+}
+
+pub async fn get_folder_search(
+    State(app_state): State<AppState>,
+    Path(folder_path): Path<String>,
+    Query(query): Query<FolderListingQuery>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> Response {
+    search_response(&app_state, &folder_path, query, &uri, &headers).await
+}
+
+async fn search_response(app_state: &AppState, folder_path: &str, query: FolderListingQuery, uri: &Uri, headers: &HeaderMap) -> Response {
+    let category = app_state.file_service.category_or_default(query.category);
+    refresh_if_requested(app_state, query.fresh, &category).await;
+    let mut response = match folder_search(app_state, folder_path, query.q.as_deref(), &category).await {
+        Ok(response) => response,
+        Err(e) => return e.into_response(),
+    };
+    sort_and_filter_items(&mut response.items, query.sort_by, query.order, query.file_type.as_deref());
+    JsonFormat::from_request(uri, headers).json(&response)
+}
+
+// 캐시된 전체 목록에서 prefix 아래 파일명을 대소문자 구분 없이 부분 일치 검색
+async fn folder_search(app_state: &AppState, folder_path: &str, q: Option<&str>, category: &str) -> Result<FolderSearchResponse, ApiError> {
+    let query = q.map(str::trim).unwrap_or_default();
+    if query.is_empty() {
+        return Err(ApiError::bad_request("Search query 'q' is required"));
     }
+    let path = normalize_path(folder_path);
+    info!("Searching folder '{}' for '{}' with category: {}", path, query, category);
+
+    let (key, prefix) = if path.is_empty() {
+        ("*".to_string(), String::new())
+    } else {
+        (format!("{}/", path), format!("{}/", path))
+    };
+    let files = app_state.file_service.get_r2_folder_files_with_category(&key, category).await
+        .map_err(|e| {
+            error!("Failed to search folder {}: {}", path, e);
+            ApiError::internal(format!("Failed to search folder: {}", e))
+        })?;
+
+    let needle = query.to_lowercase();
+    let items = files.into_iter()
+        .filter(|item| item.key.starts_with(&prefix) && !item.key.ends_with('/'))
+        .filter_map(|item| {
+            let filename = item.key.rsplit('/').next().unwrap_or(&item.key).to_string();
+            if !filename.to_lowercase().contains(&needle) {
+                return None;
+            }
+            Some(FolderItem {
+                file_type: Some(get_file_type(&filename)),
+                name: filename,
+                url: Some(app_state.file_service.file_url(&item.key)),
+                path: item.key,
+                item_type: "file".to_string(),
+                size: Some(item.value.size),
+                modified_at: item.value.modified_date,
+                children_count: None,
+            })
+        })
+        .collect();
+
+    Ok(FolderSearchResponse { path, query: query.to_string(), items })
 }
 
+// 정렬/필터 후 페이지를 잘라 응답 (본문 형태는 그대로, 페이지 정보는 헤더로)
+fn paginated_listing(headers: &HeaderMap, uri: &Uri, mut response: FolderContentsResponse, page_params: PageParams) -> Response {
+    let page = page_params.paginate(std::mem::take(&mut response.items));
+    let page_headers = page.headers(uri);
+    response.items = page.items;
+
+    let format = JsonFormat::from_request(uri, headers);
+    let mut listing = json_with_etag(headers, &response, format);
+    listing.headers_mut().extend(page_headers);
+    listing
+}
+
+// R2 갱신이 실패해 만료된 캐시로 응답하는 중이면 Warning 헤더로 알림
+async fn with_stale_warning(app_state: &AppState, category: &str, mut response: Response) -> Response {
+    if app_state.file_service.is_serving_stale(category).await {
+        response.headers_mut().insert(axum::http::header::WARNING, HeaderValue::from_static("110 - \"Response is Stale\""));
+    }
+    response
+}
+
+// project_list.yaml에서 book_id의 커리큘럼/월 조회 (파일이 없거나 매핑이 없으면 None)
+fn lookup_curriculum(project_list: &ProjectListCache, book_id: &str) -> (Option<String>, Option<String>) {
+    match project_list.get() {
+        Ok(project_list) => match find_stage_and_month(&project_list, book_id) {
+            Some((curriculum, month)) => (Some(curriculum), Some(month)),
+            None => (None, None),
+        },
+        Err(e) => {
+            tracing::warn!("Could not resolve curriculum for {}: {}", book_id, e);
+            (None, None)
+        }
+    }
+}
+
+// multipart 폼의 불리언 값 ("true", "1", "on")
+fn parse_form_bool(value: &str) -> bool {
+    matches!(value.trim().to_lowercase().as_str(), "true" | "1" | "on" | "yes")
+}
+
+// overwrite=false 업로드가 이미 있는 키에 걸렸으면 409 응답
+pub(crate) fn key_exists_response(error: &anyhow::Error) -> Option<Response> {
+    error
+        .downcast_ref::<KeyExists>()
+        .map(|exists| ApiError::from(exists.clone()).into_response())
+}
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+fn asset_too_large_response(max_bytes: u64) -> Response {
+    ApiError::payload_too_large(format!("파일이 너무 큽니다 (최대 {}MB)", max_bytes / (1024 * 1024)))
+        .with_detail("max_bytes", max_bytes)
+        .into_response()
+}
 
 pub async fn create_asset(
     State(app_state): State<AppState>,
+    user: Option<Extension<AdminUser>>,
+    headers: HeaderMap,
     mut multipart: Multipart,
 ) -> impl IntoResponse {
+    let actor = actor_name(user.as_ref().map(|Extension(user)| user));
+
+    // 같은 사용자가 같은 Idempotency-Key로 재시도하면 처음 결과를 반환
+    let idempotency_key = headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|key| !key.is_empty())
+        .map(|key| format!("{}:{}", actor, key));
+    // 업로드 전에 키를 예약해 처리 중인 요청과 동시에 들어온 재시도는 409로 거절
+    let idempotency_guard = match &idempotency_key {
+        Some(key) => match app_state.idempotency_store.reserve(key) {
+            Reservation::Reserved(guard) => Some(guard),
+            Reservation::InFlight => {
+                info!("create_asset for idempotency key {} is still in progress", key);
+                return ApiError::new(StatusCode::CONFLICT, "REQUEST_IN_PROGRESS", "같은 Idempotency-Key 요청을 처리 중입니다")
+                    .into_response();
+            }
+            Reservation::Completed(body) => {
+                info!("Replaying create_asset result for idempotency key {}", key);
+                return (
+                    StatusCode::OK,
+                    [(IDEMPOTENT_REPLAYED_HEADER, "true")],
+                    Json(body),
+                ).into_response();
+            }
+        },
+        None => None,
+    };
     let file_service = &app_state.file_service;
     let mut book_id = String::new();
     let mut title = String::new();
-    let mut category = String::from("reengkigo"); // 기본값
+    let mut category = app_state.file_service.default_category().to_string(); // 기본값
     let mut files = Vec::new();
     let mut subtitles_json = String::new();
+    let mut youtube_links_json = String::new();
+    let mut overwrite = false;
 
     // Parse multipart data
-    while let Some(mut field) = multipart.next_field().await.unwrap_or(None) {
+    let max_bytes = app_state.config.server.max_body_bytes as u64;
+    loop {
+        let mut field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) if e.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+                return asset_too_large_response(max_bytes);
+            }
+            Err(e) => {
+                return ApiError::bad_request(format!("요청 형식 오류: {}", e)).into_response();
+            }
+        };
         match field.name().unwrap_or("") {
             "book_id" => book_id = field.text().await.unwrap_or_default(),
             "title" => title = field.text().await.unwrap_or_default(),
-            "category" => category = field.text().await.unwrap_or(String::from("reengkigo")),
+            "category" => category = app_state.file_service.category_or_default(field.text().await.ok()),
             "subtitles" => subtitles_json = field.text().await.unwrap_or_default(),
+            "youtube_links" => youtube_links_json = field.text().await.unwrap_or_default(),
+            "overwrite" => overwrite = parse_form_bool(&field.text().await.unwrap_or_default()),
             "cover_image" | "video_file" => {
-                let filename = field.file_name().unwrap_or("unknown").to_string();
+                // 비워 둔 파일 입력란 (선택 사항인 표지 등)
+                if is_blank_file_name(field.file_name()) {
+                    continue;
+                }
+                let filename = field.file_name().unwrap_or_default().to_string();
                 let field_name = field.name().unwrap_or("unknown").to_string();
                 info!("Processing {} file: {}", field_name, filename);
+
+                let upload_config = &app_state.config.upload;
+                let allowed = if field_name == "cover_image" {
+                    upload_config.is_allowed_image(&filename)
+                } else {
+                    upload_config.is_allowed_video(&filename)
+                };
+                if !allowed {
+                    return ApiError::bad_request(format!("허용되지 않는 파일 형식입니다: {}", filename)).into_response();
+                }
                 
-                // Stream the file in chunks to handle large files efficiently
-                let mut data = Vec::new();
-                let mut total_size = 0u64;
-                
-                // Process field chunks efficiently to avoid memory issues
-                while let Ok(Some(chunk)) = field.chunk().await {
-                    total_size += chunk.len() as u64;
-                    
-                    // Check file size limit during streaming (2GB)
-                    if total_size > 2 * 1024 * 1024 * 1024 {
-                        error!("File too large during streaming: {} ({:.2}GB)", filename, total_size as f64 / (1024.0 * 1024.0 * 1024.0));
-                        return (
-                            StatusCode::PAYLOAD_TOO_LARGE,
-                            Json(CreateAssetResponse {
-                                success: false,
-                                asset_id: None,
-                                message: format!("파일이 너무 큽니다: {:.2}GB (최대 2GB)", total_size as f64 / (1024.0 * 1024.0 * 1024.0)),
-                                cover_image_url: None,
-                                video_url: None,
-                            })
-                        ).into_response();
+                // Small files stay in memory; larger ones spill to a temp file (removed on drop)
+                let mut body_limit_exceeded = false;
+                let chunks = (&mut field).inspect(|chunk| {
+                    if matches!(chunk, Err(e) if e.status() == StatusCode::PAYLOAD_TOO_LARGE) {
+                        body_limit_exceeded = true;
                     }
-                    
-                    data.extend_from_slice(&chunk);
-                }
+                });
+                let data = match SpooledFile::from_stream(
+                    chunks,
+                    upload_config.spill_threshold_bytes,
+                    max_bytes,
+                    upload_config.spill_dir.as_deref(),
+                ).await {
+                    Ok(data) => data,
+                    Err(e) => {
+                        error!("Failed to receive {} file {}: {}", field_name, filename, e);
+                        let error = match e {
+                            // 파일 한도 또는 라우트의 DefaultBodyLimit 초과
+                            SpoolError::TooLarge { .. } => return asset_too_large_response(max_bytes),
+                            SpoolError::Read(_) if body_limit_exceeded => return asset_too_large_response(max_bytes),
+                            SpoolError::Read(_) => ApiError::bad_request(format!("파일 읽기 실패: {}", e)),
+                            SpoolError::Io(_) => ApiError::internal(format!("임시 파일 저장 실패: {}", e)),
+                        };
+                        return error.into_response();
+                    }
+                };
                 
-                let file_size_mb = total_size as f64 / (1024.0 * 1024.0);
-                info!("Streamed {} file: {} ({:.2}MB)", field_name, filename, file_size_mb);
+                if data.is_empty() {
+                    continue;
+                }
+                let file_size_mb = data.len() as f64 / (1024.0 * 1024.0);
+                info!("Received {} file: {} ({:.2}MB, {})", field_name, filename, file_size_mb,
+                    if data.is_spilled() { "spilled to disk" } else { "in memory" });
                 
-                files.push((filename, data.into()));
+                files.push((field_name == "cover_image", filename, data));
             }
             _ => {}
         }
@@ -159,53 +515,92 @@ pub async fn create_asset(
 
     // Validate required fields (video or audio file is required, cover image is optional)
     if book_id.is_empty() || title.is_empty() {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(CreateAssetResponse {
-                success: false,
-                asset_id: None,
-                message: "필수 필드 누락: 교재 ID, 제목".to_string(),
-                cover_image_url: None,
-                video_url: None,
-            })
-        ).into_response();
+        return ApiError::bad_request("필수 필드 누락: 교재 ID, 제목").into_response();
+    }
+
+    // 제목은 `{book_id}/{title}/`의 한 폴더 이름이어야 함 (슬래시가 있으면 하위 폴더가 생김)
+    if !is_single_segment(&title) {
+        return ApiError::bad_request("제목에 경로 구분자(/, \\)를 사용할 수 없습니다").into_response();
     }
 
     // Rename files and validate types
+    // 첫 번째 표지는 {title}{ext} (이미지 프록시가 찾는 이름), 나머지 표지는 {title}_cover{n}{ext}
     let mut renamed_files = Vec::new();
     let mut has_video = false;
+    let mut cover_number = 0;
+    let mut new_filenames = HashSet::new();
 
-    for (original_filename, data) in files {
+    for (is_cover, original_filename, data) in files {
         let extension = original_filename.rfind('.').map(|i| &original_filename[i..]).unwrap_or("");
-        let new_filename = format!("{}{}", title, extension);
+        let new_filename = if is_cover {
+            cover_number += 1;
+            if cover_number == 1 {
+                format!("{}{}", title, extension)
+            } else {
+                format!("{}_cover{}{}", title, cover_number, extension)
+            }
+        } else {
+            format!("{}{}", title, extension)
+        };
         
-        let lower = new_filename.to_lowercase();
-        if lower.ends_with(".mp4") || lower.ends_with(".mov") || lower.ends_with(".avi")
-            || lower.ends_with(".mkv") || lower.ends_with(".webm")
-            || lower.ends_with(".mp3") || lower.ends_with(".wav") || lower.ends_with(".m4a") || lower.ends_with(".aac") {
+        if app_state.config.upload.is_allowed_video(&new_filename) {
             has_video = true;
         }
+
+        // 같은 키로 올라가면 앞의 파일을 덮어쓰므로 업로드 전에 거절
+        if !new_filenames.insert(new_filename.clone()) {
+            return ApiError::bad_request(format!("같은 이름으로 저장되는 파일이 여러 개입니다: {}", new_filename)).into_response();
+        }
         
         renamed_files.push((new_filename, data));
     }
 
+    // 파일이 하나도 없는 경우도 여기서 걸러짐
+    if !has_video {
+        return ApiError::bad_request("비디오 또는 오디오 파일이 필요합니다").into_response();
+    }
+
+    // 자막은 형식을 확인한 뒤에만 subtitle.json으로 올림
+    let subtitles: Vec<SubtitleData> = if subtitles_json.is_empty() {
+        Vec::new()
+    } else {
+        match serde_json::from_str(&subtitles_json) {
+            Ok(subtitles) => subtitles,
+            Err(e) => {
+                return ApiError::bad_request(format!("subtitles 형식 오류: {}", e)).into_response();
+            }
+        }
+    };
+
     // Add subtitle.json file if subtitles are provided
     if !subtitles_json.is_empty() {
-        let subtitle_data: axum::body::Bytes = subtitles_json.as_bytes().to_vec().into();
-        renamed_files.push(("subtitle.json".to_string(), subtitle_data));
+        renamed_files.push(("subtitle.json".to_string(), SpooledFile::from(subtitles_json.as_bytes().to_vec())));
     }
 
-    if !has_video {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(CreateAssetResponse {
-                success: false,
-                asset_id: None,
-                message: "비디오 또는 오디오 파일이 필요합니다".to_string(),
-                cover_image_url: None,
-                video_url: None,
-            })
-        ).into_response();
+    // Add youtube_links.json, with missing titles filled in
+    let youtube_links: Vec<YouTubeLink> = if youtube_links_json.is_empty() {
+        Vec::new()
+    } else {
+        match serde_json::from_str(&youtube_links_json) {
+            Ok(links) => app_state.youtube_service.process_youtube_links(links).await,
+            Err(e) => {
+                return ApiError::bad_request(format!("youtube_links 형식 오류: {}", e)).into_response();
+            }
+        }
+    };
+    // 업로드 전에 목록 상한 검사 (표지는 실제로 저장될 서로 다른 표지 키 수)
+    let cover_count = renamed_files.iter()
+        .map(|(filename, _)| filename.as_str())
+        .filter(|filename| app_state.config.upload.is_allowed_image(filename))
+        .collect::<HashSet<_>>()
+        .len();
+    if let Err(e) = app_state.asset_service.check_limits(Some(cover_count), Some(youtube_links.len()), Some(subtitles.len())) {
+        return ApiError::from(e).into_response();
+    }
+
+    if !youtube_links.is_empty() {
+        let links_data = serde_json::to_vec(&youtube_links).unwrap_or_default();
+        renamed_files.push(("youtube_links.json".to_string(), links_data.into()));
     }
 
     let full_path = format!("{}/{}/", book_id, title);
@@ -213,65 +608,94 @@ pub async fn create_asset(
     info!("Starting upload to external API: path={}, total_files={}", full_path, renamed_files.len());
     let total_size_mb: f64 = renamed_files.iter().map(|(_, data)| data.len() as f64 / (1024.0 * 1024.0)).sum();
     info!("Total upload size: {:.2}MB", total_size_mb);
-    
-    match file_service.upload_file(renamed_files, None, &full_path, Some(&category)).await {
+    let file_keys: Vec<String> = renamed_files.iter().map(|(name, _)| format!("{}{}", full_path, name)).collect();
+
+    let upload_result = file_service.upload_spooled_files(renamed_files, None, &full_path, Some(&category), overwrite).await;
+    app_state.audit_service.record(&actor, "create_asset", &full_path, upload_result.is_ok()).await;
+
+    match upload_result {
         Ok(response) => {
+            let upload_config = &app_state.config.upload;
             let cover_image_url = response.uploaded.iter()
-                .find(|f| f.filename.to_lowercase().contains(".png") || 
-                          f.filename.to_lowercase().contains(".jpg") || 
-                          f.filename.to_lowercase().contains(".jpeg"))
+                .find(|f| upload_config.is_allowed_image(&f.filename))
                 .map(|f| f.url.clone());
                 
             let video_url = response.uploaded.iter()
-                .find(|f| {
-                    let lower = f.filename.to_lowercase();
-                    lower.contains(".mp4") || lower.contains(".mov") || lower.contains(".avi")
-                        || lower.contains(".mkv") || lower.contains(".webm")
-                        || lower.contains(".mp3") || lower.contains(".wav") || lower.contains(".m4a") || lower.contains(".aac")
-                })
+                .find(|f| upload_config.is_allowed_video(&f.filename))
                 .map(|f| f.url.clone());
 
-            info!("Asset created successfully: {} - {}", book_id, title);
-            
-            (
-                StatusCode::OK,
-                Json(CreateAssetResponse {
-                    success: true,
-                    asset_id: Some(format!("{}_{}", book_id, title)),
-                    message: "에셋이 성공적으로 생성되었습니다".to_string(),
-                    cover_image_url,
-                    video_url,
+            let files: Vec<UploadedFileSummary> = response.uploaded.iter()
+                .map(|f| UploadedFileSummary {
+                    filename: f.filename.clone(),
+                    size: f.size,
+                    url: f.url.clone(),
                 })
-            ).into_response()
+                .collect();
+
+            let asset_id = Asset::asset_id(&book_id, &title);
+            let cover_keys: Vec<String> = file_keys.iter()
+                .filter(|key| get_file_type(key) == "image")
+                .cloned()
+                .collect();
+            let (curriculum, month) = lookup_curriculum(&app_state.project_list, &book_id);
+            let created = app_state.asset_service.create_asset(Asset {
+                id: asset_id.clone(),
+                book_id: book_id.clone(),
+                title: title.clone(),
+                category: category.clone(),
+                curriculum,
+                month,
+                files: file_keys.clone(),
+                cover_image_url: cover_image_url.clone(),
+                covers: cover_keys,
+                primary_cover: None,
+                video_url: video_url.clone(),
+                subtitles,
+                youtube_links,
+                created_by: actor.clone(),
+                created_at: chrono::Utc::now(),
+                updated_by: None,
+                updated_at: None,
+            }).await;
+            // 업로드 전에 검사했지만 그래도 실패하면 올린 파일을 되돌리고 오류 반환
+            // (idempotency 예약은 complete 없이 풀려 같은 키로 다시 시도 가능)
+            if let Err(e) = created {
+                error!("Asset metadata rejected after upload, rolling back {} file(s): {}", file_keys.len(), e);
+                file_service.unlink_files(None, &file_keys).await;
+                return ApiError::from(e).into_response();
+            }
+            info!("Asset created successfully: {} - {} (by {})", book_id, title, actor);
+            
+            let response = CreateAssetResponse {
+                asset_id,
+                message: "에셋이 성공적으로 생성되었습니다".to_string(),
+                cover_image_url,
+                video_url,
+                created_by: actor,
+                files,
+            };
+            // 성공한 결과만 저장 - 실패하면 guard가 drop되며 예약이 풀려 같은 키로 다시 시도할 수 있음
+            if let (Some(guard), Ok(body)) = (idempotency_guard, serde_json::to_value(&response)) {
+                guard.complete(body);
+            }
+
+            (StatusCode::OK, Json(response)).into_response()
         }
         Err(err) => {
+            if let Some(response) = key_exists_response(&err) {
+                return response;
+            }
             error!("Asset creation failed: {}", err);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(CreateAssetResponse {
-                    success: false,
-                    asset_id: None,
-                    message: format!("에셋 생성 실패: {}", err),
-                    cover_image_url: None,
-                    video_url: None,
-                })
-            ).into_response()
+            ApiError::internal(format!("에셋 생성 실패: {}", err)).into_response()
         }
     }
 }
 
 
-async fn build_folder_structure(app_state: &AppState, target_path: &str) -> Result<FolderContentsResponse, String> {
-    build_folder_structure_with_category(app_state, target_path, "reengkigo").await
-}
-
 async fn build_folder_structure_with_category(app_state: &AppState, target_path: &str, category: &str) -> Result<FolderContentsResponse, String> {
     // 경로 정규화 (빈 문자열은 루트)
-    let normalized_path = if target_path.is_empty() || target_path == "/" {
-        ""
-    } else {
-        target_path.trim_start_matches('/').trim_end_matches('/')
-    };
+    let normalized_path = normalize_path(target_path);
+    let normalized_path = normalized_path.as_str();
     
     info!("Building folder structure for path: '{}'", normalized_path);
     
@@ -279,17 +703,19 @@ async fn build_folder_structure_with_category(app_state: &AppState, target_path:
         // 루트 레벨: 교재ID (첫 번째 레벨) 추출 - 최적화된 방법 사용
         let folder_names = app_state.file_service.get_folder_structure_with_category("", category).await
             .map_err(|e| format!("Failed to get folder structure: {}", e))?;
+        let mut modified_dates = app_state.file_service.get_folder_modified_dates_with_category("", category).await
+            .unwrap_or_default();
         
         let folder_items: Vec<FolderItem> = folder_names.into_iter()
             .map(|folder_name| {
                 FolderItem {
                     name: folder_name.clone(),
+                    modified_at: modified_dates.remove(&folder_name),
                     path: folder_name,
                     item_type: "folder".to_string(),
                     size: None,
                     file_type: None,
                     url: None,
-                    modified_at: None,
                     children_count: None, // 정확한 개수는 생략하여 성능 향상
                 }
             })
@@ -315,6 +741,8 @@ async fn build_folder_structure_with_category(app_state: &AppState, target_path:
             // 1. 폴더 구조 가져오기
             let folder_names = app_state.file_service.get_folder_structure_with_category(curriculum_id, category).await
                 .map_err(|e| format!("Failed to get folder structure: {}", e))?;
+            let mut modified_dates = app_state.file_service.get_folder_modified_dates_with_category(curriculum_id, category).await
+                .unwrap_or_default();
             
             let mut all_items: Vec<FolderItem> = folder_names.into_iter()
                 .map(|folder_name| {
@@ -325,7 +753,7 @@ async fn build_folder_structure_with_category(app_state: &AppState, target_path:
                         size: None,
                         file_type: None,
                         url: None,
-                        modified_at: None,
+                        modified_at: modified_dates.remove(&folder_name),
                         children_count: None,
                     }
                 })
@@ -353,7 +781,7 @@ async fn build_folder_structure_with_category(app_state: &AppState, target_path:
                                         item_type: "file".to_string(),
                                         size: Some(item.value.size),
                                         file_type: Some(file_type),
-                                        url: Some(format!("https://reengki-assets-r2-worker.reengkigo.workers.dev/content/{}/{}", curriculum_id, item.key.split('/').last().unwrap_or(&item.key))),
+                                        url: Some(format!("https://reengki-assets-r2-worker.reengkigo.workers.dev/content/{}/{}", curriculum_id, item.key.rsplit('/').next().unwrap_or(&item.key))),
                                         modified_at: item.value.modified_date.clone(),
                                         children_count: None,
                                     })
@@ -485,9 +913,7 @@ async fn build_folder_structure_with_category(app_state: &AppState, target_path:
 fn get_file_type(filename: &str) -> String {
     let lower_filename = filename.to_lowercase();
 
-    if lower_filename.ends_with(".png") || lower_filename.ends_with(".jpg") ||
-       lower_filename.ends_with(".jpeg") || lower_filename.ends_with(".gif") ||
-       lower_filename.ends_with(".webp") {
+    if media::image_content_type_for(&lower_filename).is_some() {
         "image".to_string()
     } else if lower_filename.ends_with(".mp4") || lower_filename.ends_with(".mov") ||
               lower_filename.ends_with(".avi") || lower_filename.ends_with(".mkv") ||
@@ -507,34 +933,6 @@ fn get_file_type(filename: &str) -> String {
     }
 }
 
-fn build_breadcrumbs(path: &str) -> Vec<BreadcrumbItem> {
-    let mut breadcrumbs = vec![
-        BreadcrumbItem {
-            name: "Home".to_string(),
-            path: "".to_string(),
-        }
-    ];
-    
-    if !path.is_empty() {
-        let parts: Vec<&str> = path.split('/').collect();
-        let mut current_path = String::new();
-        
-        for (i, part) in parts.iter().enumerate() {
-            if i > 0 {
-                current_path.push('/');
-            }
-            current_path.push_str(part);
-            
-            breadcrumbs.push(BreadcrumbItem {
-                name: part.to_string(),
-                path: current_path.clone(),
-            });
-        }
-    }
-    
-    breadcrumbs
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeleteItemRequest {
     pub key: String,
@@ -542,30 +940,39 @@ pub struct DeleteItemRequest {
 
 pub async fn delete_item(
     State(app_state): State<AppState>,
+    Extension(user): Extension<AdminUser>,
     Json(request): Json<DeleteItemRequest>,
 ) -> impl IntoResponse {
+    delete_item_response(&app_state, &user, request).await
+}
+
+pub async fn delete_item_by_query(
+    State(app_state): State<AppState>,
+    Extension(user): Extension<AdminUser>,
+    Query(request): Query<DeleteItemRequest>,
+) -> impl IntoResponse {
+    delete_item_response(&app_state, &user, request).await
+}
+
+async fn delete_item_response(app_state: &AppState, user: &AdminUser, request: DeleteItemRequest) -> Response {
     info!("Deleting item with key: {}", request.key);
     
-    match app_state.file_service.unlink_file(&request.key).await {
+    let result = app_state.file_service.unlink_file(None, &request.key).await;
+    app_state.audit_service.record(&user.account, "delete_item", &request.key, result.is_ok()).await;
+
+    match result {
         Ok(_) => {
             info!("Successfully deleted item: {}", request.key);
             (
                 StatusCode::OK,
                 Json(serde_json::json!({
-                    "success": true,
                     "message": "Item deleted successfully"
                 }))
             ).into_response()
         }
         Err(error) => {
             error!("Failed to delete item {}: {}", request.key, error);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "success": false,
-                    "error": format!("Failed to delete item: {}", error)
-                }))
-            ).into_response()
+            ApiError::internal(format!("Failed to delete item: {}", error)).into_response()
         }
     }
 }
@@ -576,47 +983,20 @@ pub struct SubtitleRequest {
     pub title: String,
 }
 
-async fn find_subtitle_filename(book_id: &str, title: &str) -> String {
-    find_subtitle_filename_with_category(book_id, title, "reengkigo").await
-}
+async fn find_subtitle_filename_with_category(file_service: &FileService, book_id: &str, title: &str, category: &str) -> String {
+    let folder_key = format!("{}/{}", book_id, title);
 
-async fn find_subtitle_filename_with_category(book_id: &str, title: &str, category: &str) -> String {
-    let folder_url = format!("https://assets.reengkigo.com/folder-files?key={}/{}&category={}", book_id, title, category);
-    
-    match reqwest::get(&folder_url).await {
-        Ok(folder_response) => {
-            if folder_response.status().is_success() {
-                match folder_response.text().await {
-                    Ok(folder_content) => {
-                        // Try parsing as direct array first
-                        if let Ok(files) = serde_json::from_str::<serde_json::Value>(&folder_content) {
-                            let files_array = if files.is_array() {
-                                files.as_array().unwrap()
-                            } else if let Some(items) = files.get("items").and_then(|i| i.as_array()) {
-                                items
-                            } else {
-                                return "subtitle.json".to_string();
-                            };
-                            
-                            // Find subtitle file
-                            for file in files_array {
-                                if let Some(key) = file.get("key").and_then(|k| k.as_str()) {
-                                    let filename = key.split('/').last().unwrap_or("");
-                                    if filename.to_lowercase().ends_with(".json") && 
-                                       (filename.to_lowercase().contains("subtitle") || 
-                                        filename.to_lowercase().contains("sub")) {
-                                        info!("Found subtitle file: {} for category: {}", filename, category);
-                                        return filename.to_string();
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Err(_) => {}
-                }
+    if let Ok(Some(keys)) = file_service.list_folder_keys_with_category(&folder_key, category).await {
+        // Find subtitle file
+        for key in &keys {
+            let filename = key.rsplit('/').next().unwrap_or("");
+            if filename.to_lowercase().ends_with(".json") && 
+               (filename.to_lowercase().contains("subtitle") || 
+                filename.to_lowercase().contains("sub")) {
+                info!("Found subtitle file: {} for category: {}", filename, category);
+                return filename.to_string();
             }
         }
-        Err(_) => {}
     }
     
     // Default fallback
@@ -624,17 +1004,19 @@ async fn find_subtitle_filename_with_category(book_id: &str, title: &str, catego
 }
 
 pub async fn get_subtitle_data(
-    State(_app_state): State<AppState>,
+    State(app_state): State<AppState>,
     Path((book_id, title)): Path<(String, String)>,
     Query(query): Query<CategoryQuery>
 ) -> impl IntoResponse {
-    info!("Getting subtitle data for: {}/{} with category: {}", book_id, title, query.category);
+    let file_service = &app_state.file_service;
+    let category = file_service.category_or_default(query.category);
+    info!("Getting subtitle data for: {}/{} with category: {}", book_id, title, category);
     
     // Find the actual subtitle filename
-    let subtitle_filename = find_subtitle_filename_with_category(&book_id, &title, &query.category).await;
-    let subtitle_url = format!("https://r2-api.reengki.com/download/{}/{}/{}", book_id, title, subtitle_filename);
+    let subtitle_filename = find_subtitle_filename_with_category(file_service, &book_id, &title, &category).await;
+    let subtitle_path = format!("{}/{}/{}", book_id, title, subtitle_filename);
     
-    match reqwest::get(&subtitle_url).await {
+    match file_service.fetch_subtitle(&subtitle_path).await {
         Ok(response) => {
             if response.status().is_success() {
                 match response.json::<Vec<SubtitleData>>().await {
@@ -643,9 +1025,8 @@ pub async fn get_subtitle_data(
                         (
                             StatusCode::OK,
                             Json(serde_json::json!({
-                                "success": true,
-                                "data": subtitle_data,
-                                "path": format!("{}/{}/{}", book_id, title, subtitle_filename),
+                                "subtitles": subtitle_data,
+                                "path": subtitle_path,
                                 "filename": subtitle_filename
                             }))
                         ).into_response()
@@ -655,20 +1036,18 @@ pub async fn get_subtitle_data(
                         (
                             StatusCode::OK,
                             Json(serde_json::json!({
-                                "success": true,
-                                "data": [],
+                                "subtitles": [],
                                 "message": "자막 데이터 파싱 실패"
                             }))
                         ).into_response()
                     }
                 }
             } else {
-                info!("Subtitle file not found: {} (status: {})", subtitle_url, response.status());
+                info!("Subtitle file not found: {} (status: {})", subtitle_path, response.status());
                 (
                     StatusCode::OK,
                     Json(serde_json::json!({
-                        "success": true,
-                        "data": [],
+                        "subtitles": [],
                         "message": "자막 파일이 없습니다"
                     }))
                 ).into_response()
@@ -676,106 +1055,344 @@ pub async fn get_subtitle_data(
         }
         Err(error) => {
             error!("Failed to fetch subtitle data: {}", error);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "success": false,
-                    "error": format!("자막 데이터 요청 실패: {}", error)
-                }))
-            ).into_response()
+            ApiError::internal(format!("자막 데이터 요청 실패: {}", error)).into_response()
         }
     }
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub struct ImageQuery {
+    /// Serve the configured placeholder image instead of a 404 (GET only)
+    #[serde(default)]
+    pub placeholder: bool,
+    pub category: Option<String>,
+}
+
+/// Cover image behind `GET`/`HEAD /api/image/:book_id/:title`
+struct CoverImage {
+    item: R2WorkerFileItem,
+    content_type: &'static str,
+}
+
+// GET/HEAD 공통 규칙: 카테고리의 캐시된 목록에서 {book_id}/{title}/{title}.{이미지 확장자}를
+// IMAGE_FORMATS 순서로 찾음 (확장자 대소문자 무시)
+async fn resolve_cover_image(file_service: &FileService, book_id: &str, title: &str, category: &str) -> anyhow::Result<Option<CoverImage>> {
+    let stem = format!("{}/{}/{}.", book_id, title, title);
+    let candidates = file_service
+        .find_cached_files(category, |key| key.strip_prefix(stem.as_str()).and_then(media::image_content_type).is_some())
+        .await?;
+    Ok(media::IMAGE_FORMATS.iter().find_map(|(extension, content_type)| {
+        candidates
+            .iter()
+            .find(|item| item.key[stem.len()..].eq_ignore_ascii_case(extension))
+            .map(|item| CoverImage { item: item.clone(), content_type })
+    }))
+}
+
 pub async fn get_image_content(
-    State(_app_state): State<AppState>,
+    State(app_state): State<AppState>,
     Path((book_id, title)): Path<(String, String)>,
+    Query(query): Query<ImageQuery>,
 ) -> impl IntoResponse {
     info!("Getting image content for: {}/{}", book_id, title);
-    
-    // Find image file first
-    match reqwest::get(&format!("https://r2-api.reengki.com/folder-files?key={}/{}", book_id, title)).await {
-        Ok(folder_response) => {
-            if !folder_response.status().is_success() {
-                return (
-                    StatusCode::NOT_FOUND,
-                    "Folder not found".to_string()
-                ).into_response();
-            }
-            
-            match folder_response.text().await {
-                Ok(folder_content) => {
-                    // Parse folder content to find image file
-                    if let Ok(folder_data) = serde_json::from_str::<serde_json::Value>(&folder_content) {
-                        if let Some(files) = folder_data.as_array() {
-                            // Find image file
-                            for file in files {
-                                if let Some(key) = file.get("key").and_then(|k| k.as_str()) {
-                                    let filename = key.split('/').last().unwrap_or("");
-                                    if filename.to_lowercase().ends_with(".jpg") || 
-                                       filename.to_lowercase().ends_with(".jpeg") || 
-                                       filename.to_lowercase().ends_with(".png") {
-                                        
-                                        // Get the image extension
-                                        let extension = filename.split('.').last().unwrap_or("jpg");
-                                        let image_url = format!("https://r2-api.reengki.com/download/{}/{}/{}.{}", book_id, title, title, extension);
-                                        
-                                        info!("Loading image from: {}", image_url);
-                                        
-                                        // Fetch the image
-                                        match reqwest::get(&image_url).await {
-                                            Ok(image_response) => {
-                                                if image_response.status().is_success() {
-                                                    match image_response.bytes().await {
-                                                        Ok(image_bytes) => {
-                                                            // Create response with proper content type
-                                                            let mut headers = HeaderMap::new();
-                                                            let content_type = match extension.to_lowercase().as_str() {
-                                                                "png" => "image/png",
-                                                                "jpg" | "jpeg" => "image/jpeg",
-                                                                _ => "image/jpeg"
-                                                            };
-                                                            headers.insert("content-type", HeaderValue::from_static(content_type));
-                                                            headers.insert("cache-control", HeaderValue::from_static("public, max-age=3600"));
-                                                            
-                                                            return (StatusCode::OK, headers, image_bytes.to_vec()).into_response();
-                                                        }
-                                                        Err(e) => {
-                                                            error!("Failed to read image bytes: {}", e);
-                                                            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read image").into_response();
-                                                        }
-                                                    }
-                                                } else {
-                                                    info!("Image not found: {} (status: {})", image_url, image_response.status());
-                                                    return (StatusCode::NOT_FOUND, "Image not found").into_response();
-                                                }
-                                            }
-                                            Err(e) => {
-                                                error!("Failed to fetch image: {}", e);
-                                                return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch image").into_response();
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
+    let file_service = &app_state.file_service;
+    let category = file_service.category_or_default(query.category);
+
+    let image = match resolve_cover_image(file_service, &book_id, &title, &category).await {
+        Ok(Some(image)) => image,
+        Ok(None) => return image_not_found(&app_state, query.placeholder, "No image file found").await,
+        Err(e) => {
+            error!("Failed to fetch folder data: {}", e);
+            return ApiError::internal("Failed to fetch folder data").into_response();
+        }
+    };
+
+    let image_path = &image.item.key;
+    info!("Loading image from: {}", image_path);
+    match file_service.fetch_image(image_path).await {
+        Ok(image_response) => {
+            if image_response.status().is_success() {
+                match image_response.bytes().await {
+                    Ok(image_bytes) => {
+                        // Create response with proper content type
+                        let mut headers = HeaderMap::new();
+                        headers.insert("content-type", HeaderValue::from_static(image.content_type));
+                        headers.insert("cache-control", HeaderValue::from_static("public, max-age=3600"));
+                        
+                        (StatusCode::OK, headers, image_bytes.to_vec()).into_response()
+                    }
+                    Err(e) => {
+                        error!("Failed to read image bytes: {}", e);
+                        ApiError::internal("Failed to read image").into_response()
                     }
-                    
-                    (StatusCode::NOT_FOUND, "No image file found".to_string()).into_response()
-                }
-                Err(e) => {
-                    error!("Failed to read folder response: {}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read folder data".to_string()).into_response()
                 }
+            } else {
+                info!("Image not found: {} (status: {})", image_path, image_response.status());
+                image_not_found(&app_state, query.placeholder, "Image not found").await
             }
         }
         Err(e) => {
-            error!("Failed to fetch folder data: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch folder data".to_string()).into_response()
+            error!("Failed to fetch image: {}", e);
+            ApiError::internal("Failed to fetch image").into_response()
+        }
+    }
+}
+
+// 이미지를 받지 않고 캐시된 R2 목록의 크기/수정일로 헤더만 응답
+pub async fn head_image_content(
+    State(app_state): State<AppState>,
+    Path((book_id, title)): Path<(String, String)>,
+    Query(query): Query<ImageQuery>,
+) -> impl IntoResponse {
+    let category = app_state.file_service.category_or_default(query.category);
+
+    let CoverImage { item, content_type } = match resolve_cover_image(&app_state.file_service, &book_id, &title, &category).await {
+        Ok(Some(image)) => image,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            error!("Failed to look up image for {}/{} in cached listing: {}", book_id, title, e);
+            return ApiError::internal("Failed to look up image").into_response();
+        }
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.insert(axum::http::header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers.insert(axum::http::header::CONTENT_LENGTH, HeaderValue::from(item.value.size));
+    headers.insert(axum::http::header::CACHE_CONTROL, HeaderValue::from_static("public, max-age=3600"));
+    let modified = item.value.modified_date.as_deref().unwrap_or("");
+    let etag = format!("W/{}", etag::etag_for(format!("{}:{}:{}", item.key, item.value.size, modified).as_bytes()));
+    if let Ok(etag) = HeaderValue::from_str(&etag) {
+        headers.insert(axum::http::header::ETAG, etag);
+    }
+    if let Some(last_modified) = http_date(modified).and_then(|date| HeaderValue::from_str(&date).ok()) {
+        headers.insert(axum::http::header::LAST_MODIFIED, last_modified);
+    }
+    (StatusCode::OK, headers).into_response()
+}
+
+// R2 modified_date (RFC 3339) -> HTTP-date
+fn http_date(modified: &str) -> Option<String> {
+    let modified = chrono::DateTime::parse_from_rfc3339(modified).ok()?;
+    Some(modified.with_timezone(&chrono::Utc).format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+}
+
+// 이미지가 없을 때: placeholder=true면 설정된 대체 이미지(200), 아니면 404
+async fn image_not_found(app_state: &AppState, placeholder: bool, message: &'static str) -> Response {
+    if placeholder {
+        let path = &app_state.config.server.placeholder_image;
+        match tokio::fs::read(path).await {
+            Ok(bytes) => {
+                let content_type = path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .and_then(media::image_content_type)
+                    .unwrap_or("application/octet-stream");
+                let mut headers = HeaderMap::new();
+                headers.insert("content-type", HeaderValue::from_static(content_type));
+                // 실제 이미지가 올라오면 바로 보이도록 캐시하지 않음
+                headers.insert("cache-control", HeaderValue::from_static("no-cache"));
+                return (StatusCode::OK, headers, bytes).into_response();
+            }
+            Err(e) => error!("Failed to read placeholder image {}: {}", path.display(), e),
+        }
+    }
+    ApiError::not_found(message).into_response()
+}
+
+pub async fn get_categories(State(app_state): State<AppState>) -> impl IntoResponse {
+    info!("Listing available categories");
+
+    match app_state.file_service.list_categories().await {
+        Ok(categories) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "categories": categories
+            }))
+        ).into_response(),
+        Err(error) => {
+            error!("Failed to list categories: {}", error);
+            ApiError::internal(format!("Failed to list categories: {}", error)).into_response()
+        }
+    }
+}
+
+/// Deepest folder tree `/api/folder-tree` will build in one response
+pub const MAX_FOLDER_TREE_DEPTH: usize = 4;
+
+#[derive(Deserialize)]
+pub struct FolderTreeQuery {
+    pub category: Option<String>, // 없으면 설정된 기본 카테고리
+    #[serde(default = "default_folder_tree_depth")]
+    pub depth: usize,
+}
+
+fn default_folder_tree_depth() -> usize {
+    2
+}
+
+// 사이드바 트리를 한 번에 그리기 위한 중첩 폴더 구조 (depth는 1..=MAX_FOLDER_TREE_DEPTH)
+pub async fn get_folder_tree(
+    State(app_state): State<AppState>,
+    Query(query): Query<FolderTreeQuery>,
+    format: JsonFormat,
+) -> impl IntoResponse {
+    let category = app_state.file_service.category_or_default(query.category);
+    let depth = query.depth.clamp(1, MAX_FOLDER_TREE_DEPTH);
+    info!("Getting folder tree (depth: {}) with category: {}", depth, category);
+
+    match app_state.file_service.get_folder_tree_with_category(&category, depth).await {
+        Ok(folders) => format.json(&serde_json::json!({
+            "depth": depth,
+            "folders": folders
+        })),
+        Err(error) => {
+            error!("Failed to get folder tree: {}", error);
+            ApiError::internal(format!("Failed to get folder tree: {}", error)).into_response()
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RecentFilesQuery {
+    pub category: Option<String>, // 없으면 모든 카테고리
+    #[serde(default = "default_recent_limit")]
+    pub limit: usize,
+}
+
+fn default_recent_limit() -> usize {
+    20
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecentFileItem {
+    pub name: String,
+    pub path: String,
+    pub category: String,
+    pub size: u64,
+    pub file_type: String,
+    pub url: String,
+    pub modified_at: Option<String>,
+}
+
+// "최근 활동" 패널용: 수정일 최신순 파일 목록 (수정일 없는 파일은 마지막)
+pub async fn get_recent_files(
+    State(app_state): State<AppState>,
+    Query(query): Query<RecentFilesQuery>,
+) -> impl IntoResponse {
+    let limit = query.limit.clamp(1, 100);
+    let category = query.category.filter(|category| !category.is_empty());
+    info!("Getting {} recent files (category: {:?})", limit, category);
+
+    match app_state.file_service.recent_files(category.as_deref(), limit).await {
+        Ok(files) => {
+            let files: Vec<RecentFileItem> = files.into_iter()
+                .map(|item| {
+                    let name = item.key.rsplit('/').next().unwrap_or(&item.key).to_string();
+                    RecentFileItem {
+                        file_type: get_file_type(&name),
+                        name,
+                        url: format!("https://r2-api.reengki.com/file?key={}", item.key),
+                        path: item.key,
+                        category: item.value.category.unwrap_or_default(),
+                        size: item.value.size,
+                        modified_at: item.value.modified_date,
+                    }
+                })
+                .collect();
+            Json(serde_json::json!({ "files": files })).into_response()
+        }
+        Err(error) => {
+            error!("Failed to get recent files: {}", error);
+            ApiError::internal(format!("Failed to get recent files: {}", error)).into_response()
         }
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UploadConfigResponse {
+    pub max_body_bytes: usize,
+    pub allowed_image_types: Vec<String>,
+    pub allowed_video_types: Vec<String>,
+    pub allowed_extensions: Vec<String>,
+    pub max_concurrent_uploads: usize,
+}
+
+// 프론트엔드가 업로드 제한을 하드코딩하지 않도록 현재 설정을 그대로 제공
+pub async fn get_upload_config(State(app_state): State<AppState>) -> impl IntoResponse {
+    let config = &app_state.config;
+    Json(UploadConfigResponse {
+        max_body_bytes: config.server.max_body_bytes,
+        allowed_image_types: config.upload.allowed_image_types.clone(),
+        allowed_video_types: config.upload.allowed_video_types.clone(),
+        allowed_extensions: config.upload.allowed_extensions.clone(),
+        max_concurrent_uploads: config.upload.max_concurrent_uploads,
+    })
+}
+
+// project_list.yaml 제공 (파일이 없으면 원인을 알 수 있는 JSON 오류 반환)
+pub async fn get_project_list() -> impl IntoResponse {
+    match read_project_list(std::path::Path::new(PROJECT_LIST_PATH)) {
+        Ok(content) => (
+            StatusCode::OK,
+            [("content-type", "application/yaml")],
+            content
+        ).into_response(),
+        Err(error) => {
+            error!("Failed to serve project list: {}", error);
+            let message = match error {
+                ProjectListError::NotFound { .. } => "project_list.yaml not found".to_string(),
+                other => other.to_string(),
+            };
+            ApiError::internal(message)
+                .with_detail("hint", "Place project_list.yaml in the server's working directory and retry")
+                .into_response()
+        }
+    }
+}
+
+// project_list.yaml 변경 후 캐시 다시 읽기
+pub async fn reload_project_list(State(app_state): State<AppState>) -> impl IntoResponse {
+    match app_state.project_list.reload() {
+        Ok(project_list) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "stages": project_list.len()
+            }))
+        ).into_response(),
+        Err(error) => {
+            error!("Failed to reload project list: {}", error);
+            ApiError::internal(error.to_string()).into_response()
+        }
+    }
+}
+
+// 폴더 변경 이벤트 스트림 (SSE) - 업로드/삭제 시 "folder-changed" 이벤트 전송
+pub async fn folder_events(
+    State(app_state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    info!("Folder events subscriber connected");
+    let receiver = app_state.file_service.subscribe_folder_events();
+
+    let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(change) => {
+                    let event = Event::default()
+                        .event("folder-changed")
+                        .json_data(&change)
+                        .unwrap_or_else(|_| Event::default().event("folder-changed").data(change.path.clone()));
+                    return Some((Ok(event), receiver));
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    info!("Folder events subscriber lagged, skipped {} events", skipped);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
 // 캐시 관리 API 엔드포인트들
 
 pub async fn clear_cache(State(app_state): State<AppState>) -> impl IntoResponse {
@@ -786,7 +1403,6 @@ pub async fn clear_cache(State(app_state): State<AppState>) -> impl IntoResponse
     (
         StatusCode::OK,
         Json(serde_json::json!({
-            "success": true,
             "message": "All cache cleared successfully"
         }))
     ).into_response()
@@ -796,20 +1412,45 @@ pub async fn get_cache_stats(State(app_state): State<AppState>) -> impl IntoResp
     info!("Getting cache statistics");
     
     let (total, expired) = app_state.file_service.get_cache_stats().await;
+    let counters = app_state.file_service.cache_counters();
     
     (
         StatusCode::OK,
         Json(serde_json::json!({
-            "success": true,
             "stats": {
                 "total_entries": total,
                 "expired_entries": expired,
-                "active_entries": total - expired
+                "active_entries": total - expired,
+                "hits": counters.hits,
+                "misses": counters.misses,
+                "background_refreshes": counters.background_refreshes,
+                "decompressions": counters.decompressions,
+                "hit_ratio": counters.hit_ratio()
             }
         }))
     ).into_response()
 }
 
+pub async fn get_dashboard_stats(State(app_state): State<AppState>) -> impl IntoResponse {
+    match app_state.dashboard_service.get_dashboard_stats().await {
+        Ok(stats) => (StatusCode::OK, Json(stats)).into_response(),
+        Err(e) => {
+            error!("Failed to get dashboard stats: {}", e);
+            ApiError::internal(format!("Failed to get dashboard stats: {}", e)).into_response()
+        }
+    }
+}
+
+pub async fn get_system_health(State(app_state): State<AppState>) -> impl IntoResponse {
+    match app_state.dashboard_service.get_system_health().await {
+        Ok(health) => (StatusCode::OK, Json(health)).into_response(),
+        Err(e) => {
+            error!("Failed to get system health: {}", e);
+            ApiError::internal(format!("Failed to get system health: {}", e)).into_response()
+        }
+    }
+}
+
 pub async fn cleanup_expired_cache(State(app_state): State<AppState>) -> impl IntoResponse {
     info!("Cleaning up expired cache entries");
     
@@ -818,7 +1459,6 @@ pub async fn cleanup_expired_cache(State(app_state): State<AppState>) -> impl In
     (
         StatusCode::OK,
         Json(serde_json::json!({
-            "success": true,
             "message": "Expired cache entries cleaned up"
         }))
     ).into_response()
@@ -826,38 +1466,52 @@ pub async fn cleanup_expired_cache(State(app_state): State<AppState>) -> impl In
 
 pub async fn upload_single_file(
     State(app_state): State<AppState>,
+    Extension(user): Extension<AdminUser>,
     mut multipart: Multipart,
 ) -> impl IntoResponse {
     info!("Single file upload request received");
     
     let mut file_data: Option<(String, axum::body::Bytes)> = None;
     let mut full_path = String::new();
-    let mut category = String::from("reengkigo"); // 기본값
+    let mut category = app_state.file_service.default_category().to_string(); // 기본값
+    let mut bucket = String::new();
+    let mut overwrite = false;
     
-    while let Some(field) = multipart.next_field().await.unwrap_or(None) {
+    loop {
+        let field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => return ApiError::from(e).into_response(),
+        };
         match field.name().unwrap_or("") {
             "file" => {
-                let filename = field.file_name().unwrap_or("unknown").to_string();
+                if is_blank_file_name(field.file_name()) {
+                    continue;
+                }
+                let filename = field.file_name().unwrap_or_default().to_string();
                 let data = match field.bytes().await {
                     Ok(bytes) => bytes,
+                    Err(e) if e.status() == StatusCode::PAYLOAD_TOO_LARGE => return ApiError::from(e).into_response(),
                     Err(e) => {
                         error!("Failed to read file data: {}", e);
-                        return (
-                            StatusCode::BAD_REQUEST,
-                            Json(serde_json::json!({
-                                "success": false,
-                                "error": "Failed to read file data"
-                            }))
-                        ).into_response();
+                        return ApiError::bad_request("Failed to read file data").into_response();
                     }
                 };
-                file_data = Some((filename, data));
+                if !data.is_empty() {
+                    file_data = Some((filename, data));
+                }
             }
             "fullpath" | "full_path" => {
                 full_path = field.text().await.unwrap_or_default();
             }
             "category" => {
-                category = field.text().await.unwrap_or(String::from("reengkigo"));
+                category = app_state.file_service.category_or_default(field.text().await.ok());
+            }
+            "overwrite" => {
+                overwrite = parse_form_bool(&field.text().await.unwrap_or_default());
+            }
+            "bucket" => {
+                bucket = field.text().await.unwrap_or_default();
             }
             _ => {}
         }
@@ -865,15 +1519,18 @@ pub async fn upload_single_file(
     
     if let Some((filename, bytes)) = file_data {
         if full_path.is_empty() {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "success": false,
-                    "error": "Missing fullpath parameter"
-                }))
-            ).into_response();
+            return ApiError::bad_request(MISSING_FULLPATH_MESSAGE).into_response();
         }
+
+        let bucket = match app_state.file_service.requested_bucket(&bucket) {
+            Ok(bucket) => bucket,
+            Err(e) => return ApiError::from(e).into_response(),
+        };
         
+        if !app_state.config.upload.is_allowed(&filename) {
+            return ApiError::bad_request(format!("File type not allowed: {}", filename)).into_response();
+        }
+
         info!("Uploading file '{}' to full path: '{}'", filename, full_path);
         info!("Full path length: {}, contains slash: {}", full_path.len(), full_path.contains('/'));
         
@@ -888,13 +1545,15 @@ pub async fn upload_single_file(
         
         // Use the file service to upload the file
         let files = vec![(filename.clone(), bytes)];
-        match app_state.file_service.upload_file(files, None, base_path, Some(&category)).await {
+        let upload_result = app_state.file_service.upload_file(files, bucket, base_path, Some(&category), overwrite).await;
+        app_state.audit_service.record(&user.account, "upload_file", &full_path, upload_result.is_ok()).await;
+
+        match upload_result {
             Ok(response) => {
                 info!("File uploaded successfully to: {}", full_path);
                 (
                     StatusCode::OK,
                     Json(serde_json::json!({
-                        "success": true,
                         "message": "File uploaded successfully",
                         "file_path": full_path,
                         "filename": filename,
@@ -903,24 +1562,15 @@ pub async fn upload_single_file(
                 ).into_response()
             }
             Err(e) => {
+                if let Some(response) = key_exists_response(&e) {
+                    return response;
+                }
                 error!("Failed to upload file: {}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({
-                        "success": false,
-                        "error": format!("Upload failed: {}", e)
-                    }))
-                ).into_response()
+                ApiError::internal(format!("Upload failed: {}", e)).into_response()
             }
         }
     } else {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "success": false,
-                "error": "No file provided"
-            }))
-        ).into_response()
+        ApiError::bad_request(NO_FILE_MESSAGE).into_response()
     }
 }
 
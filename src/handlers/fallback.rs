@@ -0,0 +1,18 @@
+use axum::{
+    http::{Method, StatusCode, Uri},
+    response::{IntoResponse, Response},
+};
+
+use crate::error::ApiError;
+
+fn is_api_path(path: &str) -> bool {
+    path == "/api" || path.starts_with("/api/")
+}
+
+// 등록되지 않은 경로: API는 JSON 404, 그 외는 빈 404
+pub async fn not_found(method: Method, uri: Uri) -> Response {
+    if is_api_path(uri.path()) {
+        return ApiError::not_found(format!("No API route for {} {}", method, uri.path())).into_response();
+    }
+    StatusCode::NOT_FOUND.into_response()
+}
@@ -1,11 +1,13 @@
 use crate::{
     dto::file::DeleteFileRequest,
+    error::ApiError,
     services::file::FileService,
+    utils::multipart::{is_blank_file_name, MISSING_FULLPATH_MESSAGE, NO_FILE_MESSAGE},
 };
 use axum::{
-    extract::{Multipart, State},
+    extract::{Multipart, Query, State},
     http::StatusCode,
-    response::{IntoResponse, Json},
+    response::{IntoResponse, Json, Response},
 };
 use std::sync::Arc;
 use tracing::error;
@@ -15,7 +17,7 @@ use tracing::error;
     path = "/upload",
     responses(
         (status = 200, description = "Files uploaded successfully"),
-        (status = 400, description = "Bad request"),
+        (status = 400, description = "Bad request or bucket not allowed"),
         (status = 500, description = "Internal server error")
     ),
     tag = "file"
@@ -28,12 +30,25 @@ pub async fn upload_file(
     let mut bucket = String::new();
     let mut full_path = String::new();
 
-    while let Some(field) = multipart.next_field().await.unwrap() {
+    loop {
+        let field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => return ApiError::from(e).into_response(),
+        };
         match field.name().unwrap_or("") {
             "file" => {
-                let filename = field.file_name().unwrap_or("unknown").to_string();
-                let data = field.bytes().await.unwrap();
-                files.push((filename, data));
+                if is_blank_file_name(field.file_name()) {
+                    continue;
+                }
+                let filename = field.file_name().unwrap_or_default().to_string();
+                let data = match field.bytes().await {
+                    Ok(data) => data,
+                    Err(e) => return ApiError::from(e).into_response(),
+                };
+                if !data.is_empty() {
+                    files.push((filename, data));
+                }
             }
             "bucket" => bucket = field.text().await.unwrap_or_default(),
             "fullpath" => full_path = field.text().await.unwrap_or_default(),
@@ -41,22 +56,22 @@ pub async fn upload_file(
         }
     }
 
-    if files.is_empty() || full_path.is_empty() {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "Missing required fields: file or fullpath"}))
-        ).into_response();
+    if files.is_empty() {
+        return ApiError::bad_request(NO_FILE_MESSAGE).into_response();
+    }
+    if full_path.is_empty() {
+        return ApiError::bad_request(MISSING_FULLPATH_MESSAGE).into_response();
     }
 
-    let bucket_param = if bucket.is_empty() { None } else { Some(bucket.as_str()) };
-    match file_service.upload_file(files, bucket_param, &full_path, None).await {
+    let bucket_param = match file_service.requested_bucket(&bucket) {
+        Ok(bucket) => bucket,
+        Err(e) => return ApiError::from(e).into_response(),
+    };
+    match file_service.upload_file(files, bucket_param, &full_path, None, true).await {
         Ok(response) => (StatusCode::OK, Json(response)).into_response(),
         Err(e) => {
             error!("Upload failed: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": format!("Upload failed: {}", e)}))
-            ).into_response()
+            ApiError::internal(format!("Upload failed: {}", e)).into_response()
         }
     }
 }
@@ -76,15 +91,40 @@ pub async fn delete_file(
     State(file_service): State<Arc<FileService>>,
     Json(request): Json<DeleteFileRequest>,
 ) -> impl IntoResponse {
-    let bucket_param = if request.bucket.is_empty() { None } else { Some(request.bucket.as_str()) };
+    delete_file_response(&file_service, request).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/files",
+    params(
+        ("bucket" = Option<String>, Query, description = "Bucket name (defaults to the configured bucket; others must be in external_api.allowed_buckets)"),
+        ("key" = String, Query, description = "Key of the file to delete")
+    ),
+    responses(
+        (status = 200, description = "File deleted successfully"),
+        (status = 400, description = "Bad request"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "file"
+)]
+pub async fn delete_file_by_query(
+    State(file_service): State<Arc<FileService>>,
+    Query(request): Query<DeleteFileRequest>,
+) -> impl IntoResponse {
+    delete_file_response(&file_service, request).await
+}
+
+async fn delete_file_response(file_service: &FileService, request: DeleteFileRequest) -> Response {
+    let bucket_param = match file_service.requested_bucket(&request.bucket) {
+        Ok(bucket) => bucket,
+        Err(e) => return ApiError::from(e).into_response(),
+    };
     match file_service.delete_file(bucket_param, &request.key).await {
         Ok(response) => (StatusCode::OK, Json(response)).into_response(),
         Err(e) => {
             error!("Delete file failed: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": format!("Delete file failed: {}", e)}))
-            ).into_response()
+            ApiError::internal(format!("Delete file failed: {}", e)).into_response()
         }
     }
-}
\ No newline at end of file
+}
@@ -2,6 +2,7 @@ use axum::{extract::Query, response::Json};
 use serde::Deserialize;
 use crate::{
     dto::folder_category::{FolderCategoryListResponse, FolderCategoryResponse, CourseGroupResponse},
+    error::ApiError,
     models::folder_category::FolderCategory,
 };
 
@@ -17,41 +18,23 @@ pub async fn get_folder_categories(
     
     if query.group_by_course.unwrap_or(false) {
         let grouped = CourseGroupResponse::group_by_course(categories);
-        Json(serde_json::json!({
-            "success": true,
-            "data": grouped
-        }))
+        Json(serde_json::json!(grouped))
     } else {
         let responses: Vec<FolderCategoryResponse> = categories
             .into_iter()
             .map(FolderCategoryResponse::from)
             .collect();
             
-        Json(serde_json::json!({
-            "success": true,
-            "data": FolderCategoryListResponse {
-                categories: responses
-            }
+        Json(serde_json::json!(FolderCategoryListResponse {
+            categories: responses
         }))
     }
 }
 
 pub async fn get_category_by_stage_code(
     axum::extract::Path(stage_code): axum::extract::Path<String>,
-) -> Json<serde_json::Value> {
-    match FolderCategory::categorize_by_stage_code(&stage_code) {
-        Some(category) => {
-            let response = FolderCategoryResponse::from(category);
-            Json(serde_json::json!({
-                "success": true,
-                "data": response
-            }))
-        }
-        None => {
-            Json(serde_json::json!({
-                "success": false,
-                "error": "Stage code not found"
-            }))
-        }
-    }
+) -> Result<Json<FolderCategoryResponse>, ApiError> {
+    FolderCategory::categorize_by_stage_code(&stage_code)
+        .map(|category| Json(FolderCategoryResponse::from(category)))
+        .ok_or_else(|| ApiError::not_found("Stage code not found"))
 }
\ No newline at end of file
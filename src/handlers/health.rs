@@ -0,0 +1,44 @@
+use std::sync::atomic::Ordering;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+
+use crate::utils::build_info::BuildInfo;
+use crate::AppState;
+
+// Liveness: the process is up and serving requests
+pub async fn health_check() -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "ok"
+        }))
+    )
+}
+
+// Readiness: startup tasks have completed and traffic can be routed here
+pub async fn readiness_check(State(app_state): State<AppState>) -> impl IntoResponse {
+    if app_state.ready.load(Ordering::Acquire) {
+        (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "status": "ready"
+            }))
+        )
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({
+                "status": "starting"
+            }))
+        )
+    }
+}
+
+// Which build is running: app name/version from config, git commit and build time from build.rs
+pub async fn version(State(app_state): State<AppState>) -> impl IntoResponse {
+    (StatusCode::OK, Json(BuildInfo::new(&app_state.config.app)))
+}
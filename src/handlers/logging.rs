@@ -0,0 +1,32 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use serde::Deserialize;
+use tracing::error;
+
+use crate::{error::ApiError, AppState};
+
+#[derive(Debug, Deserialize)]
+pub struct LogLevelRequest {
+    pub directive: String,
+}
+
+pub async fn set_log_level(
+    State(app_state): State<AppState>,
+    Json(request): Json<LogLevelRequest>,
+) -> impl IntoResponse {
+    match app_state.observability.set_log_level(&request.directive) {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "directive": app_state.observability.current_log_level()
+            }))
+        ).into_response(),
+        Err(e) => {
+            error!("Failed to change log level: {}", e);
+            ApiError::bad_request(e.to_string()).into_response()
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use serde::Deserialize;
+use tracing::{error, info};
+
+use crate::{dto::asset::FilterParams, error::ApiError, services::asset::find_orphans, AppState};
+
+#[derive(Debug, Deserialize)]
+pub struct OrphanScanQuery {
+    pub category: Option<String>,
+}
+
+// R2 최상위 폴더와 에셋 book_id 비교 (읽기 전용, 아무것도 삭제하지 않음)
+pub async fn scan_orphans(
+    State(app_state): State<AppState>,
+    Query(query): Query<OrphanScanQuery>,
+) -> impl IntoResponse {
+    let category = app_state.file_service.category_or_default(query.category);
+    info!("Scanning for orphaned folders/assets (category: {})", category);
+
+    let folders = match app_state.file_service.get_folder_structure_with_category("", &category).await {
+        Ok(folders) => folders,
+        Err(e) => {
+            error!("Failed to list R2 folders for orphan scan: {}", e);
+            return ApiError::bad_gateway(format!("Failed to list R2 folders: {}", e)).into_response();
+        }
+    };
+
+    let assets = app_state.asset_service.get_filtered_assets(&FilterParams::default()).await;
+    let report = find_orphans(&folders, &assets);
+    info!(
+        "Orphan scan found {} folders without assets, {} assets without folders",
+        report.folders_without_assets.len(),
+        report.assets_without_folders.len()
+    );
+
+    // 메모리 저장소는 재시작 후 비어 있으므로 그 전에 만든 폴더가 모두 고아로 보임
+    let store_ephemeral = app_state.asset_service.is_ephemeral();
+    let mut body = serde_json::json!({
+        "category": category,
+        "store_ephemeral": store_ephemeral,
+        "orphans": report
+    });
+    if store_ephemeral {
+        body["warning"] = serde_json::json!(
+            "에셋 메타데이터가 메모리에만 있어 서버 재시작 전에 만든 폴더도 folders_without_assets에 포함됩니다"
+        );
+    }
+    (StatusCode::OK, Json(body)).into_response()
+}
@@ -1,3 +1,16 @@
+//! HTTP handlers.
+//!
+//! Response convention: a successful JSON response is the result object itself and the
+//! HTTP status carries success; there is no `success`/`data` wrapper. Failures are
+//! [`crate::error::ApiError`] bodies (`{"error": {"code", "message"}}`).
+
 pub mod auth;
 pub mod file;
-pub mod dashboard;
\ No newline at end of file
+pub mod dashboard;
+pub mod fallback;
+pub mod audit;
+pub mod asset;
+pub mod health;
+pub mod logging;
+pub mod maintenance;
+pub mod upload;
\ No newline at end of file
@@ -0,0 +1,241 @@
+//! Resumable (tus-style) uploads for large files over unreliable connections.
+//!
+//! `POST /api/uploads` opens a session for a declared `Upload-Length`, each
+//! `PATCH /api/uploads/:id` appends a chunk at `Upload-Offset`, and `HEAD` reports
+//! how far the server got so an interrupted client can resume from there. The
+//! PATCH that delivers the last byte pushes the assembled file to R2; if that
+//! push fails the session and file are kept, and an empty PATCH at the final
+//! offset retries it. Sessions are only visible to the account that opened them.
+
+use axum::{
+    body::Body,
+    extract::{Extension, Path, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use tracing::{error, info};
+
+use crate::{
+    dto::upload::{CreateUploadRequest, UploadSessionResponse},
+    error::ApiError,
+    handlers::dashboard::key_exists_response,
+    models::user::AdminUser,
+    services::file::KeyExists,
+    services::upload_session::{AppendOutcome, UploadProgress, UploadSessionError, UploadTarget},
+    utils::multipart::MISSING_FULLPATH_MESSAGE,
+    AppState,
+};
+
+pub const UPLOAD_OFFSET_HEADER: HeaderName = HeaderName::from_static("upload-offset");
+pub const UPLOAD_LENGTH_HEADER: HeaderName = HeaderName::from_static("upload-length");
+
+fn header_u64(headers: &HeaderMap, name: &HeaderName) -> Result<u64, ApiError> {
+    let value = headers
+        .get(name)
+        .ok_or_else(|| ApiError::bad_request(format!("Missing {} header", name)))?;
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .ok_or_else(|| ApiError::bad_request(format!("Invalid {} header", name)))
+}
+
+fn progress_headers(progress: UploadProgress) -> [(HeaderName, HeaderValue); 3] {
+    [
+        (UPLOAD_OFFSET_HEADER, HeaderValue::from(progress.offset)),
+        (UPLOAD_LENGTH_HEADER, HeaderValue::from(progress.length)),
+        (header::CACHE_CONTROL, HeaderValue::from_static("no-store")),
+    ]
+}
+
+fn session_error_response(error: UploadSessionError) -> Response {
+    match error {
+        UploadSessionError::NotFound(_) => ApiError::not_found(error.to_string()).into_response(),
+        UploadSessionError::NotOwner(_) => ApiError::forbidden(error.to_string()).into_response(),
+        UploadSessionError::TooManySessions { limit } => {
+            ApiError::new(StatusCode::TOO_MANY_REQUESTS, "TOO_MANY_UPLOAD_SESSIONS", error.to_string())
+                .with_detail("limit", limit)
+                .into_response()
+        }
+        UploadSessionError::OffsetMismatch { expected, .. } => ApiError::conflict(error.to_string())
+            .with_detail("offset", expected)
+            .into_response(),
+        UploadSessionError::ExceedsLength { .. } | UploadSessionError::Read(_) => {
+            ApiError::bad_request(error.to_string()).into_response()
+        }
+        UploadSessionError::Io(_) => {
+            error!("Resumable upload I/O error: {}", error);
+            ApiError::internal(error.to_string()).into_response()
+        }
+    }
+}
+
+pub async fn create_upload(
+    State(app_state): State<AppState>,
+    Extension(user): Extension<AdminUser>,
+    headers: HeaderMap,
+    Json(request): Json<CreateUploadRequest>,
+) -> impl IntoResponse {
+    let length = match header_u64(&headers, &UPLOAD_LENGTH_HEADER) {
+        Ok(length) => length,
+        Err(e) => return e.into_response(),
+    };
+    let max_bytes = app_state.config.server.max_body_bytes as u64;
+    if length == 0 {
+        return ApiError::bad_request("Upload-Length must be greater than zero").into_response();
+    }
+    if length > max_bytes {
+        return ApiError::payload_too_large(format!("Upload exceeds the {} byte limit", max_bytes))
+            .with_detail("max_bytes", max_bytes)
+            .into_response();
+    }
+
+    let full_path = request.fullpath.trim();
+    if full_path.is_empty() {
+        return ApiError::bad_request(MISSING_FULLPATH_MESSAGE).into_response();
+    }
+    let (base_path, filename) = match full_path.rfind('/') {
+        Some(last_slash) => full_path.split_at(last_slash + 1),
+        None => ("", full_path),
+    };
+    if filename.is_empty() {
+        return ApiError::bad_request("fullpath must end with a file name").into_response();
+    }
+    if !app_state.config.upload.is_allowed(filename) {
+        return ApiError::bad_request(format!("File type not allowed: {}", filename)).into_response();
+    }
+    let bucket = match app_state.file_service.requested_bucket(request.bucket.as_deref().unwrap_or_default()) {
+        Ok(bucket) => bucket.map(str::to_string),
+        Err(e) => return ApiError::from(e).into_response(),
+    };
+    let category = app_state.file_service.category_or_default(request.category);
+
+    // 다 받은 뒤 R2로 올릴 때 다시 확인하지만, 큰 파일을 보내기 전에 미리 거절
+    if !request.overwrite {
+        match app_state.file_service.key_exists(bucket.as_deref(), full_path).await {
+            Ok(true) => {
+                return ApiError::from(KeyExists {
+                    key: full_path.to_string(),
+                    url: app_state.file_service.file_url(full_path),
+                }).into_response();
+            }
+            Ok(false) => {}
+            Err(e) => {
+                error!("Failed to check {} before opening an upload: {}", full_path, e);
+                return ApiError::bad_gateway(format!("Failed to check for an existing file: {}", e)).into_response();
+            }
+        }
+    }
+
+    let target = UploadTarget {
+        base_path: base_path.to_string(),
+        filename: filename.to_string(),
+        category,
+        bucket,
+        overwrite: request.overwrite,
+    };
+    let id = match app_state.upload_sessions.create(target, length, &user.account).await {
+        Ok(id) => id,
+        Err(e) => return session_error_response(e),
+    };
+    info!("Opened resumable upload {} for {} ({} bytes)", id, full_path, length);
+
+    let location = format!("/api/uploads/{}", id);
+    (
+        StatusCode::CREATED,
+        [(header::LOCATION, location)],
+        progress_headers(UploadProgress { offset: 0, length }),
+        Json(UploadSessionResponse {
+            id,
+            file_path: full_path.to_string(),
+            offset: 0,
+            length,
+        }),
+    ).into_response()
+}
+
+// 끊긴 뒤 어디부터 다시 보낼지 확인
+pub async fn upload_status(
+    State(app_state): State<AppState>,
+    Extension(user): Extension<AdminUser>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match app_state.upload_sessions.progress(&id, &user.account).await {
+        Ok(progress) => (StatusCode::OK, progress_headers(progress)).into_response(),
+        Err(e) => session_error_response(e),
+    }
+}
+
+pub async fn append_upload(
+    State(app_state): State<AppState>,
+    Extension(user): Extension<AdminUser>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    body: Body,
+) -> impl IntoResponse {
+    let offset = match header_u64(&headers, &UPLOAD_OFFSET_HEADER) {
+        Ok(offset) => offset,
+        Err(e) => return e.into_response(),
+    };
+
+    // 선언한 Upload-Length로 크기가 제한되므로 DefaultBodyLimit 대신 세션에서 검사
+    let completed = match app_state.upload_sessions.append(&id, &user.account, offset, body.into_data_stream()).await {
+        Ok(AppendOutcome::Partial(progress)) => {
+            return (StatusCode::NO_CONTENT, progress_headers(progress)).into_response();
+        }
+        Ok(AppendOutcome::Complete(completed)) => completed,
+        Err(e) => return session_error_response(e),
+    };
+
+    let target = completed.target().clone();
+    let file = completed.file();
+    let key = target.key();
+    let length = file.len();
+    info!("Resumable upload {} complete, uploading {} ({} bytes)", id, key, length);
+    let upload_result = app_state
+        .file_service
+        .upload_spooled_files(vec![(target.filename.clone(), file)], target.bucket.as_deref(), &target.base_path, Some(&target.category), target.overwrite)
+        .await;
+    app_state.audit_service.record(&user.account, "upload_file", &key, upload_result.is_ok()).await;
+
+    // 실패하면 세션과 파일을 남겨 두어 마지막 오프셋으로 다시 PATCH하면 재시도
+    match upload_result {
+        Ok(response) => {
+            completed.finish().await;
+            (
+                StatusCode::OK,
+                progress_headers(UploadProgress { offset: length, length }),
+                Json(serde_json::json!({
+                    "id": id,
+                    "file_path": key,
+                    "offset": length,
+                    "length": length,
+                    "details": response
+                })),
+            ).into_response()
+        }
+        Err(e) => {
+            // 이미 있는 키는 다시 시도해도 같으므로 세션을 닫음
+            if let Some(response) = key_exists_response(&e) {
+                completed.finish().await;
+                return response;
+            }
+            error!("Failed to upload assembled file {}, keeping the session for a retry: {}", key, e);
+            ApiError::bad_gateway(format!("Upload failed: {}", e)).into_response()
+        }
+    }
+}
+
+pub async fn cancel_upload(
+    State(app_state): State<AppState>,
+    Extension(user): Extension<AdminUser>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match app_state.upload_sessions.cancel(&id, &user.account).await {
+        Ok(()) => {
+            info!("Cancelled resumable upload {}", id);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => session_error_response(e),
+    }
+}
@@ -1,4 +1,5 @@
 pub mod config;
+pub mod error;
 pub mod utils;
 pub mod services;
 pub mod models;
@@ -7,16 +8,50 @@ pub mod dto;
 pub mod middleware;
 
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::Instant;
+use axum::{Router, routing::{delete, get, patch, post, put}};
+use axum::middleware as axum_middleware;
+use axum::extract::DefaultBodyLimit;
+use axum::Extension;
+use tower_http::compression::CompressionLayer;
+use tower_http::services::{ServeDir, ServeFile};
 use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 pub use utils::ObservabilityManager;
 pub use services::file::FileService;
+pub use services::idempotency::IdempotencyStore;
+pub use services::audit::AuditService;
+pub use services::asset::AssetService;
+pub use services::auth::AuthService;
+pub use services::dashboard::DashboardService;
+pub use services::project_list::ProjectListCache;
+pub use services::upload_session::UploadSessionStore;
+pub use services::user::UserService;
+pub use services::youtube::YouTubeService;
 pub use config::AppConfig;
 
 #[derive(Clone)]
 pub struct AppState {
     pub file_service: Arc<FileService>,
+    pub audit_service: Arc<AuditService>,
+    pub asset_service: Arc<AssetService>,
+    pub dashboard_service: Arc<DashboardService>,
+    /// `Idempotency-Key` results for asset creation
+    pub idempotency_store: Arc<IdempotencyStore>,
+    /// Open resumable (`/api/uploads`) sessions
+    pub upload_sessions: Arc<UploadSessionStore>,
+    pub user_service: Arc<UserService>,
+    /// Built once at startup; owns the revocation list checked on every authenticated request
+    pub auth_service: Arc<AuthService>,
+    pub project_list: Arc<ProjectListCache>,
+    pub youtube_service: Arc<YouTubeService>,
     pub config: Arc<AppConfig>,
     pub observability: Arc<ObservabilityManager>,
+    /// Set once startup tasks have finished; gates `/ready`
+    pub ready: Arc<AtomicBool>,
+    /// Process start, for uptime reporting
+    pub started_at: Instant,
 }
 
 #[derive(OpenApi)]
@@ -24,6 +59,7 @@ pub struct AppState {
     paths(
         handlers::file::upload_file,
         handlers::file::delete_file,
+        handlers::file::delete_file_by_query,
     ),
     components(schemas(
         dto::file::FileUploadResponse,
@@ -35,4 +71,145 @@ pub struct AppState {
         (name = "file", description = "File management API")
     )
 )]
-pub struct ApiDoc;
\ No newline at end of file
+pub struct ApiDoc;
+
+
+pub fn create_router(state: AppState) -> Router {
+    use handlers::{asset, audit, auth, dashboard, fallback, file, health, logging, maintenance, upload};
+    use middleware::auth::AuthMiddleware;
+
+    // Upload routes get the large body limit; everything else the small JSON/form limit
+    let upload_limit = DefaultBodyLimit::max(state.config.server.max_body_bytes);
+    let json_limit = DefaultBodyLimit::max(state.config.server.max_json_body_bytes);
+
+    // File API routes - no authentication for now
+    let file_api_routes = Router::new()
+        .route("/upload", post(file::upload_file).layer(upload_limit))
+        .route("/delete-file", post(file::delete_file))
+        .route("/files", delete(file::delete_file_by_query))
+        .layer(json_limit)
+        .layer(CompressionLayer::new())
+        .with_state(state.file_service.clone());
+
+    // Health probes and build info - no authentication required
+    let health_routes = Router::new()
+        .route("/health", get(health::health_check))
+        .route("/ready", get(health::readiness_check))
+        .route("/api/version", get(health::version));
+
+    // Public auth routes - no authentication required
+    let auth_routes = Router::new()
+        .route("/", get(auth::root_handler))
+        .route("/login", get(auth::login_page))
+        .route("/login", post(auth::login_handler))
+        .layer(json_limit);
+
+    // Admin-only routes (mutations, audit) - HEAD_OFFICE / REGIONAL_MANAGER roles required
+    let admin_only_routes = Router::new()
+        .route("/api/assets", post(dashboard::create_asset).layer(upload_limit))
+        .route("/api/delete-item", post(dashboard::delete_item))
+        .route("/api/items", delete(dashboard::delete_item_by_query))
+        .route("/api/upload-file", post(dashboard::upload_single_file).layer(upload_limit))
+        .route("/api/uploads", post(upload::create_upload))
+        .route("/api/uploads/:id", patch(upload::append_upload).head(upload::upload_status).delete(upload::cancel_upload))
+        .route("/api/cache/clear", post(dashboard::clear_cache))
+        .route("/api/cache/cleanup", post(dashboard::cleanup_expired_cache))
+        .route("/api/audit-logs", get(audit::get_audit_logs))
+        .route("/api/dashboard/stats", get(dashboard::get_dashboard_stats))
+        .route("/api/dashboard/health", get(dashboard::get_system_health))
+        .route("/api/log-level", post(logging::set_log_level))
+        .route("/api/maintenance/orphans", get(maintenance::scan_orphans))
+        .route("/api/project-list/reload", post(dashboard::reload_project_list))
+        .route("/api/assets/:id", put(asset::update_asset).delete(asset::delete_asset))
+        .route("/api/assets/:id/subtitles/import", post(asset::import_subtitles))
+        .route("/api/assets/:id/youtube/reorder", patch(asset::reorder_youtube_links))
+        .route("/api/assets/:id/youtube/regenerate", post(asset::regenerate_youtube_links))
+        .route("/api/assets/:id/reconcile", post(asset::reconcile_asset))
+        .route_layer(axum_middleware::from_fn_with_state(state.auth_service.clone(), AuthMiddleware::require_admin_role))
+        .layer(CompressionLayer::new());
+
+    // Read-only routes - any active role
+    let read_only_routes = Router::new()
+        .route("/dashboard", get(dashboard::dashboard_main))
+        .route("/dashboard/assets", get(dashboard::dashboard_asset))
+        // 클라이언트 라우트 딥링크/새로고침: 등록되지 않은 /dashboard 하위 경로는 대시보드 HTML
+        .route("/dashboard/", get(dashboard::dashboard_main))
+        .route("/dashboard/*client_route", get(dashboard::dashboard_main))
+        .route("/api/folders", get(dashboard::get_root_folders))
+        // `*path`는 빈 경로와 매칭되지 않으므로 끝 슬래시만 있는 요청도 루트로 처리
+        .route("/api/folders/", get(dashboard::get_root_folders))
+        .route("/api/categories", get(dashboard::get_categories))
+        .route("/api/recent", get(dashboard::get_recent_files))
+        .route("/api/folder-tree", get(dashboard::get_folder_tree))
+        // 폴더 이름과 겹치지 않도록 요약/검색은 /api/folders 아래가 아닌 별도 경로
+        .route("/api/folder-summary", get(dashboard::get_root_folder_summary))
+        .route("/api/folder-summary/", get(dashboard::get_root_folder_summary))
+        .route("/api/folder-summary/*path", get(dashboard::get_folder_summary))
+        .route("/api/folder-search", get(dashboard::get_root_folder_search))
+        .route("/api/folder-search/", get(dashboard::get_root_folder_search))
+        .route("/api/folder-search/*path", get(dashboard::get_folder_search))
+        .route("/api/folders/events", get(dashboard::folder_events))
+        .route("/api/folders/*path", get(dashboard::get_folder_contents))
+        .route("/api/subtitle/:book_id/:title", get(dashboard::get_subtitle_data))
+        .route("/api/cache/stats", get(dashboard::get_cache_stats))
+        .route("/api/config/upload", get(dashboard::get_upload_config))
+        .route("/api/assets", get(asset::list_assets))
+        .route("/api/assets/export.csv", get(asset::export_assets_csv))
+        .route("/api/assets/:id/files", get(asset::get_asset_files))
+        .route("/api/assets/:id/diff", get(asset::get_asset_diff))
+        .route("/api/assets/:id/youtube/validate", get(asset::validate_youtube_links))
+        .route("/api/me", get(auth::me_handler))
+        .route("/api/logout", post(auth::logout_handler))
+        .route_layer(axum_middleware::from_fn_with_state(state.auth_service.clone(), AuthMiddleware::require_any_role))
+        // gzip/brotli negotiated via Accept-Encoding
+        .layer(CompressionLayer::new());
+
+    // Image proxy - already-compressed media, kept out of the compression layer
+    let media_routes = Router::new()
+        .route("/api/image/:book_id/:title", get(dashboard::get_image_content).head(dashboard::head_image_content))
+        .route_layer(axum_middleware::from_fn_with_state(state.auth_service.clone(), AuthMiddleware::require_any_role));
+
+    // Admin dashboard routes - authentication required
+    let admin_dashboard_routes = Router::new()
+        .merge(admin_only_routes)
+        .merge(read_only_routes)
+        .merge(media_routes)
+        .layer(json_limit)
+        .layer(axum_middleware::from_fn_with_state(state.auth_service.clone(), AuthMiddleware::auth_middleware))
+        // Cookie attributes for the middleware's cookie-clearing redirects
+        .layer(Extension(state.config.cookie.clone()));
+
+    // Static file serving - no authentication required
+    let static_routes = Router::new()
+        .route("/project_list.yaml", get(dashboard::get_project_list))
+        .nest_service("/static", ServeDir::new("static"))
+        .nest_service("/asset", ServeDir::new("assets").fallback(ServeFile::new(&state.config.server.placeholder_image)));
+
+    // API Documentation - Swagger UI
+    let api_docs = SwaggerUi::new("/api-docs")
+        .url("/api-docs/openapi.json", ApiDoc::openapi());
+
+    let debug_logging = state.config.app.debug;
+    let trusted_proxies: Arc<[ipnet::IpNet]> = state.config.server.trusted_proxies.clone().into();
+
+    let router = Router::new()
+        .merge(file_api_routes)
+        .merge(health_routes)
+        .merge(auth_routes)
+        .merge(admin_dashboard_routes)
+        .merge(static_routes)
+        .merge(api_docs)
+        .fallback(fallback::not_found)
+        .with_state(state)
+        // 본문 한도 초과(413)도 다른 API 오류와 같은 JSON으로
+        .layer(axum_middleware::map_response(middleware::body_limit::json_payload_too_large))
+        // 실제 클라이언트 IP (신뢰하는 프록시 뒤에서는 X-Forwarded-For / X-Real-IP)
+        .layer(axum_middleware::from_fn_with_state(trusted_proxies, middleware::client_ip::client_ip));
+
+    // Request/response logging for debugging - opt-in via app.debug
+    if debug_logging {
+        router.layer(axum_middleware::from_fn(middleware::request_logging::log_requests))
+    } else {
+        router
+    }
+}
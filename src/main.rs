@@ -1,30 +1,17 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::{Ok, Result};
 
-mod config;
-mod utils;
-mod services;
-mod models;
-mod handlers;
-mod dto;
-mod middleware;
-
-use axum::{Router, routing::{get, post}};
-use axum::middleware as axum_middleware;
-use axum::extract::DefaultBodyLimit;
-use utoipa::OpenApi;
-use utoipa_swagger_ui::SwaggerUi;
-use config::AppConfig;
-use utils::ObservabilityManager;
-use services::file::FileService;
 use tokio::signal;
 use std::net::SocketAddr;
-use tower_http::services::{ServeDir, ServeFile};
 
-use crate::handlers::{auth, file, dashboard};
-use crate::middleware::auth::AuthMiddleware;
-use server_test::ApiDoc;
+use server_test::services::auth;
+use server_test::services::file::ParallelListing;
+use server_test::services::project_list::PROJECT_LIST_PATH;
+use server_test::services::upload_session::UPLOAD_SESSION_SWEEP_INTERVAL;
+use server_test::utils::build_info::BuildInfo;
+use server_test::{create_router, utils, AppConfig, AppState, AssetService, AuditService, AuthService, DashboardService, FileService, IdempotencyStore, ObservabilityManager, ProjectListCache, UploadSessionStore, UserService, YouTubeService};
 
 /// Graceful shutdown signal handler
 /// 
@@ -57,36 +44,95 @@ async fn shutdown_signal() {
     }
 }
 
-
-#[derive(Clone)]
-pub struct AppState {
-    /// File service
-    pub file_service: Arc<FileService>,
-    /// Application configuration
-    pub config: Arc<AppConfig>,
-    /// Observability manager
-    pub observability: Arc<ObservabilityManager>,
-}
-
 #[tokio::main]
 async fn main() -> Result<()> {
+    let started_at = std::time::Instant::now();
     dotenv::dotenv().ok();
-    utils::logging::init_logging()?;
+    let logging_handles = utils::logging::init_logging()?;
     
-    let config = Arc::new(AppConfig::load()?);
-    let observability = Arc::new(ObservabilityManager::new(config.clone()).await?);
-
-    let file_service = Arc::new(FileService::new(
+    let config = AppConfig::load()?;
+    config.validate()?;
+    let app_env = std::env::var("APP_ENV").ok();
+    auth::ensure_jwt_secret(auth::is_production(config.app.debug, app_env.as_deref()))?;
+    let config = Arc::new(config);
+    let observability = Arc::new(
+        ObservabilityManager::new(config.clone()).await?
+            .with_logging_handles(logging_handles)
+    );
+
+    let file_service = Arc::new(FileService::from_config(
         config.external_api.base_url.clone(),
-        config.external_api.bucket.clone()
+        config.external_api.bucket.clone(),
+        &config.http_client,
+    ).with_upload_url(config.external_api.upload_url.clone())
+        .with_folder_files_url(config.external_api.folder_files_url.clone())
+        .with_default_category(config.external_api.default_category.clone())
+        .with_allowed_buckets(config.external_api.allowed_buckets.clone())
+        .with_cache_max_items(config.external_api.cache_max_items)
+        .with_cache_compression(config.external_api.compress_cache)
+        .with_parallel_listing(config.external_api.listing_page_size.map(|page_size| ParallelListing {
+            page_size,
+            concurrency: config.external_api.listing_concurrency,
+        })));
+
+    let user_service = Arc::new(UserService::new());
+    if let (Some(account), Some(password)) = (std::env::var("INITIAL_ADMIN_ACCOUNT").ok(), std::env::var("INITIAL_ADMIN_PASSWORD").ok()) {
+        if user_service.seed_initial_admin(&account, &password).await? {
+            tracing::info!("Seeded initial admin account: {}", account);
+        }
+    }
+
+    let auth_service = Arc::new(AuthService::from_config(&config.auth).with_user_service(user_service.clone()));
+
+    let audit_service = Arc::new(AuditService::new());
+    let asset_service = Arc::new(AssetService::new().with_limits(config.asset_limits.clone()));
+    let ready = Arc::new(AtomicBool::new(false));
+    let dashboard_service = Arc::new(DashboardService::new(
+        asset_service.clone(),
+        audit_service.clone(),
+        ready.clone(),
+        started_at,
+        BuildInfo::new(&config.app),
     ));
 
     let state = AppState {
         file_service,
+        audit_service,
+        asset_service,
+        dashboard_service,
+        idempotency_store: Arc::new(IdempotencyStore::default()),
+        upload_sessions: Arc::new(
+            UploadSessionStore::default()
+                .with_dir(config.upload.spill_dir.clone())
+                .with_max_sessions_per_owner(config.upload.max_upload_sessions_per_account),
+        ),
+        user_service,
+        auth_service,
+        project_list: Arc::new(ProjectListCache::new(PROJECT_LIST_PATH)),
+        youtube_service: Arc::new(
+            YouTubeService::new()
+                .with_oembed_url(config.external_api.youtube_oembed_url.clone())
+                .with_title_enrichment(config.external_api.enrich_youtube_titles),
+        ),
         config: config.clone(),
         observability: observability.clone(),
+        ready,
+        started_at,
     };
 
+    state.upload_sessions.clone().spawn_sweeper(UPLOAD_SESSION_SWEEP_INTERVAL);
+
+    // Startup tasks: warm the folder cache, then report ready
+    let startup_file_service = state.file_service.clone();
+    let ready = state.ready.clone();
+    tokio::spawn(async move {
+        if let Err(e) = startup_file_service.warm_cache(startup_file_service.default_category()).await {
+            tracing::warn!("Cache warm-up failed, folders will load on first request: {}", e);
+        }
+        ready.store(true, Ordering::Release);
+        tracing::info!("Startup tasks completed, server is ready");
+    });
+
     let app = create_router(state);
 
     let addr = SocketAddr::from((config.server.host.parse::<std::net::IpAddr>()?, config.server.port));
@@ -96,60 +142,13 @@ async fn main() -> Result<()> {
 
     let shutdown_signal = shutdown_signal();
 
-    axum::serve(listener, app)
+    // ConnectInfo: client_ip 미들웨어가 직접 연결한 주소를 알 수 있도록
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(shutdown_signal)
         .await?;
 
+    observability.shutdown();
+
     Ok(())
 }
 
-
-fn create_router(state: AppState) -> Router {
-    // File API routes - no authentication for now
-    let file_api_routes = Router::new()
-        .route("/upload", post(file::upload_file))
-        .route("/delete-file", post(file::delete_file))
-        .layer(DefaultBodyLimit::max(2 * 1024 * 1024 * 1024)) // 2GB limit for file uploads
-        .with_state(state.file_service.clone());
-
-    // Public auth routes - no authentication required
-    let auth_routes = Router::new()
-        .route("/", get(auth::root_handler))
-        .route("/login", get(auth::login_page))
-        .route("/login", post(auth::login_handler));
-
-    // Admin dashboard routes - authentication required
-    let admin_dashboard_routes = Router::new()
-        .route("/dashboard", get(dashboard::dashboard_main))
-        .route("/dashboard/assets", get(dashboard::dashboard_asset))
-        .route("/api/assets", post(dashboard::create_asset))
-        .route("/api/folders", get(dashboard::get_root_folders))
-        .route("/api/folders/*path", get(dashboard::get_folder_contents))
-        .route("/api/delete-item", post(dashboard::delete_item))
-        .route("/api/subtitle/:book_id/:title", get(dashboard::get_subtitle_data))
-        .route("/api/image/:book_id/:title", get(dashboard::get_image_content))
-        .route("/api/upload-file", post(dashboard::upload_single_file))
-        .route("/api/cache/clear", post(dashboard::clear_cache))
-        .route("/api/cache/stats", get(dashboard::get_cache_stats))
-        .route("/api/cache/cleanup", post(dashboard::cleanup_expired_cache))
-        .layer(DefaultBodyLimit::max(2 * 1024 * 1024 * 1024)) // 2GB limit for asset uploads
-        .layer(axum_middleware::from_fn(AuthMiddleware::auth_middleware));
-
-    // Static file serving - no authentication required
-    let static_routes = Router::new()
-        .route_service("/project_list.yaml", ServeFile::new("project_list.yaml"))
-        .nest_service("/static", ServeDir::new("static"))
-        .nest_service("/asset", ServeDir::new("assets").fallback(ServeFile::new("assets/placeholder.png")));
-
-    // API Documentation - Swagger UI
-    let api_docs = SwaggerUi::new("/api-docs")
-        .url("/api-docs/openapi.json", ApiDoc::openapi());
-
-    Router::new()
-        .merge(file_api_routes)
-        .merge(auth_routes)
-        .merge(admin_dashboard_routes)
-        .merge(static_routes)
-        .merge(api_docs)
-        .with_state(state)
-}
\ No newline at end of file
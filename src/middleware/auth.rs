@@ -1,124 +1,178 @@
+use std::sync::Arc;
+
 use axum::{
-    extract::{Request},
-    http::{header, HeaderMap, StatusCode},
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
-use serde_json::json;
 
 use crate::{
+    config::CookieConfig,
+    error::ApiError,
+    dto::auth::ClaimsResponse,
     models::user::AdminUser,
     services::auth::AuthService,
 };
 
+/// Authentication and role guards.
+///
+/// `auth_middleware` validates the token and stores the `AdminUser` and claims in
+/// request extensions. The `require_*` guards reuse that user when present and
+/// otherwise validate the token themselves, so they behave the same whether or
+/// not `auth_middleware` ran first: 401 without a valid token, 403 for a wrong role.
 pub struct AuthMiddleware;
 
+/// Response header carrying the remaining token lifetime in seconds
+pub const TOKEN_EXPIRES_IN_HEADER: &str = "x-token-expires-in";
+
+// Tokens expiring within this window get the refresh hint header
+const TOKEN_REFRESH_THRESHOLD_SECS: i64 = 30 * 60;
+
 impl AuthMiddleware {
     pub async fn auth_middleware(
+        State(auth_service): State<Arc<AuthService>>,
         headers: HeaderMap,
         mut request: Request,
         next: Next,
     ) -> Result<Response, StatusCode> {
-        // Extract token from headers (Authorization header or Cookie)
-        let token = match extract_token_from_headers(&headers) {
-            Some(token) => token,
-            None => {
-                tracing::debug!("No auth token found in headers");
-                return Ok(create_unauthorized_response(&request));
-            }
-        };
-
-        // Validate token using AuthService
-        let auth_service = AuthService::new();
-        let claims = match auth_service.validate_token(&token) {
-            Ok(claims) => {
-                tracing::debug!("Token validation successful for user: {}", claims.username);
-                claims
-            },
-            Err(e) => {
-                tracing::warn!("Token validation failed: {}", e);
-                return Ok(create_unauthorized_response(&request));
-            }
-        };
-
-        // Create AdminUser from claims for role checking
-        let admin_user = AdminUser {
-            account_id: 0, // Not available in JWT claims
-            account: claims.username.clone(),
-            role: claims.role.clone(),
-            agency_id: 0, // Not available in JWT claims
-            academy_id: 0, // Not available in JWT claims
-            is_active: true, // Token validation implies active
+        let (admin_user, claims) = match authenticate_headers(&auth_service, &headers) {
+            Some(authenticated) => authenticated,
+            None => return Ok(create_unauthorized_response(&request)),
         };
         
         tracing::debug!("Created AdminUser: {:?}", admin_user);
         tracing::debug!("AdminUser can_access_admin: {}", admin_user.can_access_admin());
 
+        let expires_at = claims.exp as i64;
+
         // Store user info in request extensions for use in handlers
         request.extensions_mut().insert(admin_user);
         request.extensions_mut().insert(claims);
 
         // Continue to the next middleware/handler
-        Ok(next.run(request).await)
+        let mut response = next.run(request).await;
+
+        // Hint the client to refresh when the token is about to expire
+        let expires_in = (expires_at - chrono::Utc::now().timestamp()).max(0);
+        if expires_in <= TOKEN_REFRESH_THRESHOLD_SECS {
+            if let Ok(value) = HeaderValue::from_str(&expires_in.to_string()) {
+                response.headers_mut().insert(TOKEN_EXPIRES_IN_HEADER, value);
+            }
+        }
+
+        Ok(response)
     }
 
     pub async fn require_admin_role(
-        _headers: HeaderMap,
-        request: Request,
+        State(auth_service): State<Arc<AuthService>>,
+        headers: HeaderMap,
+        mut request: Request,
         next: Next,
     ) -> Result<Response, StatusCode> {
         // Check if user has admin role (HEAD_OFFICE or REGIONAL_MANAGER)
-        
-        if let Some(admin_user) = request.extensions().get::<AdminUser>() {
-            tracing::debug!("require_admin_role: AdminUser found: {:?}", admin_user);
-            tracing::debug!("require_admin_role: can_access_admin: {}", admin_user.can_access_admin());
-            if admin_user.can_access_admin() {
-                return Ok(next.run(request).await);
-            }
-        } else {
-            tracing::debug!("require_admin_role: No AdminUser found in request extensions");
+        let Some(admin_user) = resolve_user(&auth_service, &headers, &mut request) else {
+            tracing::debug!("require_admin_role: no authenticated user");
+            return Ok(create_unauthorized_response(&request));
+        };
+
+        tracing::debug!("require_admin_role: can_access_admin: {}", admin_user.can_access_admin());
+        if admin_user.can_access_admin() {
+            return Ok(next.run(request).await);
         }
 
         Ok(create_forbidden_response(&request))
     }
 
     pub async fn require_director_role(
-        _headers: HeaderMap,
-        request: Request,
+        State(auth_service): State<Arc<AuthService>>,
+        headers: HeaderMap,
+        mut request: Request,
         next: Next,
     ) -> Result<Response, StatusCode> {
         // Check if user has director role
-        if let Some(admin_user) = request.extensions().get::<AdminUser>() {
-            if admin_user.can_access_director() {
-                return Ok(next.run(request).await);
-            }
+        let Some(admin_user) = resolve_user(&auth_service, &headers, &mut request) else {
+            return Ok(create_unauthorized_response(&request));
+        };
+
+        if admin_user.can_access_director() {
+            return Ok(next.run(request).await);
         }
 
         Ok(create_forbidden_response(&request))
     }
 
     pub async fn require_any_role(
-        _headers: HeaderMap,
-        request: Request,
+        State(auth_service): State<Arc<AuthService>>,
+        headers: HeaderMap,
+        mut request: Request,
         next: Next,
     ) -> Result<Response, StatusCode> {
         // Check if user has any valid role and is active
-        if let Some(admin_user) = request.extensions().get::<AdminUser>() {
-            if admin_user.is_active {
-                return Ok(next.run(request).await);
-            }
+        let Some(admin_user) = resolve_user(&auth_service, &headers, &mut request) else {
+            return Ok(create_unauthorized_response(&request));
+        };
+
+        if admin_user.is_active {
+            return Ok(next.run(request).await);
         }
 
         Ok(create_forbidden_response(&request))
     }
 }
 
+// Validate the request token and build the AdminUser from its claims
+fn authenticate_headers(auth_service: &AuthService, headers: &HeaderMap) -> Option<(AdminUser, ClaimsResponse)> {
+    // Extract token from headers (Authorization header or Cookie)
+    let Some(token) = extract_token_from_headers(headers) else {
+        tracing::debug!("No auth token found in headers");
+        return None;
+    };
+
+    // Validate token using the shared AuthService
+    let claims = match auth_service.validate_token(&token) {
+        Ok(claims) => {
+            tracing::debug!("Token validation successful for user: {}", claims.username);
+            claims
+        },
+        Err(e) => {
+            tracing::warn!("Token validation failed: {}", e);
+            return None;
+        }
+    };
+
+    // Create AdminUser from claims for role checking
+    let admin_user = AdminUser {
+        account_id: claims.account_id,
+        account: claims.username.clone(),
+        role: claims.role.clone(),
+        agency_id: claims.agency_id,
+        academy_id: claims.academy_id,
+        is_active: true, // Token validation implies active
+    };
+
+    Some((admin_user, claims))
+}
+
+// Use the AdminUser stored by auth_middleware, or authenticate here when the guard runs standalone
+fn resolve_user(auth_service: &AuthService, headers: &HeaderMap, request: &mut Request) -> Option<AdminUser> {
+    if let Some(admin_user) = request.extensions().get::<AdminUser>() {
+        return Some(admin_user.clone());
+    }
+
+    tracing::debug!("No AdminUser in request extensions, validating token in guard");
+    let (admin_user, claims) = authenticate_headers(auth_service, headers)?;
+    request.extensions_mut().insert(admin_user.clone());
+    request.extensions_mut().insert(claims);
+    Some(admin_user)
+}
+
 pub fn extract_token_from_headers(headers: &HeaderMap) -> Option<String> {
     // Try Authorization header first
     if let Some(auth_header) = headers.get(header::AUTHORIZATION) {
         if let Ok(header_str) = auth_header.to_str() {
-            if header_str.starts_with("Bearer ") {
-                let token = header_str[7..].to_string();
+            if let Some(token) = header_str.strip_prefix("Bearer ") {
+                let token = token.to_string();
                 tracing::debug!("Found Bearer token in Authorization header");
                 return Some(token);
             }
@@ -131,8 +185,8 @@ pub fn extract_token_from_headers(headers: &HeaderMap) -> Option<String> {
             tracing::debug!("Parsing cookies: {}", cookie_str);
             for cookie in cookie_str.split(';') {
                 let cookie = cookie.trim();
-                if cookie.starts_with("auth_token=") {
-                    let token = cookie[11..].to_string();
+                if let Some(token) = cookie.strip_prefix("auth_token=") {
+                    let token = token.to_string();
                     tracing::debug!("Found auth_token in cookies");
                     return Some(token);
                 }
@@ -155,53 +209,36 @@ fn is_api_request(request: &Request) -> bool {
 fn create_unauthorized_response(request: &Request) -> Response {
     if is_api_request(request) {
         // Return JSON response for API requests
-        let body = json!({
-            "error": "UNAUTHORIZED",
-            "message": "Authentication required"
-        });
-        
-        Response::builder()
-            .status(StatusCode::UNAUTHORIZED)
-            .header(header::CONTENT_TYPE, "application/json")
-            .body(body.to_string().into())
-            .unwrap()
+        ApiError::unauthorized("Authentication required").into_response()
     } else {
         // Redirect to login page for browser requests
-        Response::builder()
-            .status(StatusCode::FOUND)
-            .header(header::LOCATION, "/login")
-            .header(header::SET_COOKIE, "auth_token=; HttpOnly; SameSite=Lax; Path=/; Max-Age=0")
-            .header(header::SET_COOKIE, "auth_status=; SameSite=Lax; Path=/; Max-Age=0")
-            .body("".into())
-            .unwrap()
+        redirect_to_login(request)
     }
 }
 
 fn create_forbidden_response(request: &Request) -> Response {
     if is_api_request(request) {
         // Return JSON response for API requests
-        let body = json!({
-            "error": "FORBIDDEN",
-            "message": "Insufficient permissions"
-        });
-        
-        Response::builder()
-            .status(StatusCode::FORBIDDEN)
-            .header(header::CONTENT_TYPE, "application/json")
-            .body(body.to_string().into())
-            .unwrap()
+        ApiError::forbidden("Insufficient permissions").into_response()
     } else {
         // Redirect to login page for browser requests
-        Response::builder()
-            .status(StatusCode::FOUND)
-            .header(header::LOCATION, "/login")
-            .header(header::SET_COOKIE, "auth_token=; HttpOnly; SameSite=Lax; Path=/; Max-Age=0")
-            .header(header::SET_COOKIE, "auth_status=; SameSite=Lax; Path=/; Max-Age=0")
-            .body("".into())
-            .unwrap()
+        redirect_to_login(request)
     }
 }
 
+// Redirect to the login page, clearing the auth cookies with the configured attributes
+fn redirect_to_login(request: &Request) -> Response {
+    let cookie_config = request.extensions().get::<CookieConfig>().cloned().unwrap_or_default();
+
+    Response::builder()
+        .status(StatusCode::FOUND)
+        .header(header::LOCATION, "/login")
+        .header(header::SET_COOKIE, cookie_config.build_cookie("auth_token", "", true, 0))
+        .header(header::SET_COOKIE, cookie_config.build_cookie("auth_status", "", false, 0))
+        .body("".into())
+        .unwrap()
+}
+
 // Helper function to get current user from request
 pub fn get_current_user(request: &Request) -> Option<&AdminUser> {
     request.extensions().get::<AdminUser>()
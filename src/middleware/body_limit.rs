@@ -0,0 +1,27 @@
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+use crate::error::ApiError;
+
+/// Gives body-limit rejections the JSON error shape.
+///
+/// axum's extractors answer a body over `DefaultBodyLimit` with a plain-text 413.
+/// Responses that already carry JSON (handlers that report the limit themselves)
+/// are passed through unchanged.
+pub async fn json_payload_too_large(response: Response) -> Response {
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE {
+        return response;
+    }
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+    if is_json {
+        return response;
+    }
+
+    ApiError::payload_too_large("Request body exceeds the size limit for this endpoint").into_response()
+}
@@ -0,0 +1,74 @@
+use std::{net::{IpAddr, SocketAddr}, sync::Arc};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use ipnet::IpNet;
+
+pub const X_FORWARDED_FOR: &str = "x-forwarded-for";
+pub const X_REAL_IP: &str = "x-real-ip";
+
+/// Real client address, stored in request extensions by [`client_ip`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+fn is_trusted(ip: IpAddr, trusted_proxies: &[IpNet]) -> bool {
+    trusted_proxies.iter().any(|net| net.contains(&ip))
+}
+
+/// Resolves the client address for a connection from `peer`.
+///
+/// Forwarding headers are only believed when `peer` is a trusted proxy. The
+/// `X-Forwarded-For` chain is walked from the right, skipping further trusted
+/// proxies, so a client cannot spoof its address by prepending entries; when
+/// every hop is trusted the left-most one wins. `X-Real-IP` is used when there
+/// is no `X-Forwarded-For`.
+pub fn resolve_client_ip(peer: IpAddr, headers: &HeaderMap, trusted_proxies: &[IpNet]) -> IpAddr {
+    if !is_trusted(peer, trusted_proxies) {
+        return peer;
+    }
+
+    let chain: Vec<&str> = headers
+        .get_all(X_FORWARDED_FOR)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .collect();
+    let mut leftmost_trusted = None;
+    for entry in chain.iter().rev() {
+        // 해석할 수 없는 항목부터 왼쪽은 믿을 수 없음
+        let Ok(ip) = entry.trim().parse::<IpAddr>() else { break };
+        if !is_trusted(ip, trusted_proxies) {
+            return ip;
+        }
+        leftmost_trusted = Some(ip);
+    }
+    if let Some(ip) = leftmost_trusted {
+        return ip;
+    }
+
+    headers
+        .get(X_REAL_IP)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(peer)
+}
+
+/// Stores the [`ClientIp`] for the request, resolved against the trusted proxies.
+///
+/// Needs the server to provide `ConnectInfo<SocketAddr>`; without it (e.g. in
+/// `oneshot` tests) no `ClientIp` is stored.
+pub async fn client_ip(
+    State(trusted_proxies): State<Arc<[IpNet]>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if let Some(ConnectInfo(peer)) = request.extensions().get::<ConnectInfo<SocketAddr>>().copied() {
+        let ip = resolve_client_ip(peer.ip().to_canonical(), request.headers(), &trusted_proxies);
+        request.extensions_mut().insert(ClientIp(ip));
+    }
+    next.run(request).await
+}
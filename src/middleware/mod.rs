@@ -1,3 +1,6 @@
 pub mod auth;
+pub mod body_limit;
+pub mod client_ip;
+pub mod request_logging;
 
 pub use auth::AuthMiddleware;
\ No newline at end of file
@@ -0,0 +1,92 @@
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Instant;
+
+/// Request bodies larger than this are never buffered for logging
+pub const MAX_LOGGED_BODY_BYTES: usize = 16 * 1024;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Debug request logger, enabled by `app.debug`.
+///
+/// Logs method, path, status and duration for every request. Request bodies are
+/// logged only when they are not multipart and declare a `Content-Length` within
+/// `MAX_LOGGED_BODY_BYTES`. Credential headers are always redacted.
+pub async fn log_requests(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let started = Instant::now();
+
+    tracing::debug!(%method, %path, headers = ?redact_headers(request.headers()), "request received");
+
+    let request = if should_log_body(request.headers()) {
+        let (parts, body) = request.into_parts();
+        match axum::body::to_bytes(body, MAX_LOGGED_BODY_BYTES).await {
+            Ok(bytes) => {
+                tracing::debug!(%method, %path, body = %String::from_utf8_lossy(&bytes), "request body");
+                Request::from_parts(parts, Body::from(bytes))
+            }
+            Err(e) => {
+                // Content-Length lied about the size; the body is gone, so reject
+                tracing::warn!(%method, %path, "Failed to read request body: {}", e);
+                return StatusCode::BAD_REQUEST.into_response();
+            }
+        }
+    } else {
+        request
+    };
+
+    let response = next.run(request).await;
+
+    tracing::info!(
+        %method,
+        %path,
+        status = response.status().as_u16(),
+        duration_ms = started.elapsed().as_millis() as u64,
+        "request completed"
+    );
+
+    response
+}
+
+/// Header name/value pairs with `Authorization`, `Cookie` and `Set-Cookie` values masked
+pub fn redact_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_sensitive_header(name) {
+                REDACTED.to_string()
+            } else {
+                value.to_str().unwrap_or("<binary>").to_string()
+            };
+            (name.as_str().to_string(), value)
+        })
+        .collect()
+}
+
+fn is_sensitive_header(name: &header::HeaderName) -> bool {
+    name == header::AUTHORIZATION || name == header::COOKIE || name == header::SET_COOKIE
+}
+
+fn should_log_body(headers: &HeaderMap) -> bool {
+    let is_multipart = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|content_type| content_type.starts_with("multipart/"))
+        .unwrap_or(false);
+    if is_multipart {
+        return false;
+    }
+
+    headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok())
+        .map(|length| length > 0 && length <= MAX_LOGGED_BODY_BYTES)
+        .unwrap_or(false)
+}
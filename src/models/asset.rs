@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::dto::asset::SubtitleData;
+use crate::utils::media;
+
+/// Asset metadata recorded when an asset is created through the dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Asset {
+    pub id: String, // "{book_id}_{title}"
+    pub book_id: String,
+    pub title: String,
+    pub category: String,
+    // project_list.yaml 기준 커리큘럼/월 (매핑이 없으면 None)
+    #[serde(default)]
+    pub curriculum: Option<String>,
+    #[serde(default)]
+    pub month: Option<String>,
+    pub files: Vec<String>, // R2 keys, e.g. "book_id/title/title.mp4"
+    pub cover_image_url: Option<String>,
+    // 표지 이미지 경로 목록과 대표 표지 인덱스 (표지가 있으면 기본값 0)
+    #[serde(default)]
+    pub covers: Vec<String>,
+    #[serde(default)]
+    pub primary_cover: Option<usize>,
+    pub video_url: Option<String>,
+    #[serde(default)]
+    pub subtitles: Vec<SubtitleData>,
+    #[serde(default)]
+    pub youtube_links: Vec<YouTubeLink>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    // 마지막으로 수정한 사용자/시각 (생성 후 수정한 적이 없으면 None)
+    #[serde(default)]
+    pub updated_by: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// YouTube video attached to an asset (same shape the dashboard edits)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YouTubeLink {
+    pub thumbnail_file: String,
+    pub youtube_url: String,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+impl Asset {
+    pub fn asset_id(book_id: &str, title: &str) -> String {
+        format!("{}_{}", book_id, title)
+    }
+
+    // 수정한 사용자와 시각 기록
+    pub fn touch(&mut self, actor: &str) {
+        self.updated_by = Some(actor.to_string());
+        self.updated_at = Some(Utc::now());
+    }
+
+    // 표지 이미지 개수: 표지 목록 기준, 목록이 없는 이전 레코드는 파일 중 이미지 수
+    pub fn cover_image_count(&self) -> usize {
+        if !self.covers.is_empty() {
+            return self.covers.len();
+        }
+        self.files
+            .iter()
+            .filter(|key| media::image_content_type_for(key).is_some())
+            .count()
+    }
+}
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const AUDIT_RESULT_SUCCESS: &str = "success";
+pub const AUDIT_RESULT_FAILURE: &str = "failure";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLog {
+    pub id: String,
+    pub user: String,
+    pub action: String,
+    pub target_key: String,
+    pub timestamp: DateTime<Utc>,
+    pub result: String, // "success" or "failure"
+}
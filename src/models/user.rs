@@ -26,6 +26,14 @@ pub const ACCOUNT_TYPE_HEAD_OFFICE: u32 = 1;
 pub const ACCOUNT_TYPE_REGIONAL_MANAGER: u32 = 2;
 pub const ACCOUNT_TYPE_DIRECTOR: u32 = 3;
 
+// 인증된 사용자가 없는 쓰기 작업(시스템/마이그레이션)의 행위자 이름
+pub const SYSTEM_ACTOR: &str = "system";
+
+pub fn actor_name(user: Option<&AdminUser>) -> String {
+    user.map(|user| user.account.clone())
+        .unwrap_or_else(|| SYSTEM_ACTOR.to_string())
+}
+
 impl AdminUser {
     fn map_account_type_to_role(account_type_id: u32) -> String {
         match account_type_id {
@@ -57,6 +65,19 @@ impl AdminUser {
     }
 }
 
+impl From<User> for AdminUser {
+    fn from(user: User) -> Self {
+        Self {
+            account_id: user.id as u32,
+            account: user.username,
+            role: user.role,
+            agency_id: 0,
+            academy_id: 0,
+            is_active: user.is_active,
+        }
+    }
+}
+
 impl From<AuthInfo> for AdminUser {
     fn from(auth_info: AuthInfo) -> Self {
         Self {
@@ -0,0 +1,362 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use crate::config::AssetLimits;
+use crate::dto::asset::{FilterParams, SubtitleData, UpdateAssetRequest};
+use crate::models::asset::{Asset, YouTubeLink};
+use crate::services::youtube::{heuristic_title, YouTubeLinkStatus, YouTubeService};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AssetError {
+    #[error("Asset not found: {0}")]
+    NotFound(String),
+    #[error("primary_cover index {index} is out of range for {count} covers")]
+    PrimaryCoverOutOfRange { index: usize, count: usize },
+    #[error("{field} has {count} entries, more than the limit of {max}")]
+    LimitExceeded { field: &'static str, count: usize, max: usize },
+}
+
+fn check_count(field: &'static str, count: Option<usize>, max: usize) -> Result<(), AssetError> {
+    match count {
+        Some(count) if count > max => Err(AssetError::LimitExceeded { field, count, max }),
+        _ => Ok(()),
+    }
+}
+
+// 표지가 있으면 대표 표지를 첫 번째로 기본 설정
+fn default_primary_cover(primary_cover: Option<usize>, cover_count: usize) -> Option<usize> {
+    match primary_cover {
+        Some(index) if index < cover_count => Some(index),
+        _ if cover_count > 0 => Some(0),
+        _ => None,
+    }
+}
+
+// 에셋 메타데이터 저장소 (asset id 기준)
+#[derive(Clone, Default)]
+pub struct AssetService {
+    assets: Arc<RwLock<HashMap<String, Asset>>>,
+    limits: AssetLimits,
+}
+
+impl AssetService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_limits(mut self, limits: AssetLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Checks list sizes against the configured limits; `None` skips that list.
+    pub fn check_limits(&self, covers: Option<usize>, youtube_links: Option<usize>, subtitles: Option<usize>) -> Result<(), AssetError> {
+        check_count("covers", covers, self.limits.max_covers)?;
+        check_count("youtube_links", youtube_links, self.limits.max_youtube_links)?;
+        check_count("subtitles", subtitles, self.limits.max_subtitles)
+    }
+
+    /// True while assets live only in memory: the store starts empty after every
+    /// restart, so it does not yet cover what is already on R2
+    pub fn is_ephemeral(&self) -> bool {
+        true
+    }
+
+    // 새 에셋 등록 (목록 상한 검사 후 upsert)
+    pub async fn create_asset(&self, asset: Asset) -> Result<(), AssetError> {
+        self.check_limits(Some(asset.covers.len()), Some(asset.youtube_links.len()), Some(asset.subtitles.len()))?;
+        self.upsert(asset).await;
+        Ok(())
+    }
+
+    // 같은 id가 있으면 덮어씀
+    pub async fn upsert(&self, mut asset: Asset) {
+        asset.primary_cover = default_primary_cover(asset.primary_cover, asset.covers.len());
+        let mut assets = self.assets.write().await;
+        assets.insert(asset.id.clone(), asset);
+    }
+
+    pub async fn count(&self) -> usize {
+        self.assets.read().await.len()
+    }
+
+    pub async fn get(&self, id: &str) -> Option<Asset> {
+        self.assets.read().await.get(id).cloned()
+    }
+
+    // 요청에 포함된 필드만 변경
+    /// Runs the checks of [`Self::update_asset`] without changing anything and
+    /// returns the current asset, so callers can write R2 files before committing the update
+    pub async fn check_update(&self, id: &str, update: &UpdateAssetRequest) -> Result<Asset, AssetError> {
+        let assets = self.assets.read().await;
+        let asset = assets.get(id).ok_or_else(|| AssetError::NotFound(id.to_string()))?;
+        self.validate_update(asset, update)?;
+        Ok(asset.clone())
+    }
+
+    // 변경 전에 목록 상한과 대표 표지 인덱스 검증
+    fn validate_update(&self, asset: &Asset, update: &UpdateAssetRequest) -> Result<(), AssetError> {
+        self.check_limits(
+            update.covers.as_ref().map(Vec::len),
+            update.youtube_links.as_ref().map(Vec::len),
+            update.subtitles.as_ref().map(Vec::len),
+        )?;
+        let cover_count = update.covers.as_ref().map_or(asset.covers.len(), Vec::len);
+        if let Some(index) = update.primary_cover {
+            if index >= cover_count {
+                return Err(AssetError::PrimaryCoverOutOfRange { index, count: cover_count });
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn update_asset(&self, id: &str, update: UpdateAssetRequest, actor: &str) -> Result<Asset, AssetError> {
+        let mut assets = self.assets.write().await;
+        let asset = assets.get_mut(id).ok_or_else(|| AssetError::NotFound(id.to_string()))?;
+        self.validate_update(asset, &update)?;
+        let cover_count = update.covers.as_ref().map_or(asset.covers.len(), Vec::len);
+
+        if let Some(covers) = update.covers {
+            asset.covers = covers;
+        }
+        asset.primary_cover = default_primary_cover(update.primary_cover.or(asset.primary_cover), cover_count);
+        if let Some(curriculum) = update.curriculum {
+            asset.curriculum = Some(curriculum);
+        }
+        if let Some(month) = update.month {
+            asset.month = Some(month);
+        }
+        if let Some(subtitles) = update.subtitles {
+            asset.subtitles = subtitles;
+        }
+        if let Some(youtube_links) = update.youtube_links {
+            asset.youtube_links = youtube_links;
+        }
+        asset.touch(actor);
+        Ok(asset.clone())
+    }
+
+    // 메타데이터만 삭제 (R2 파일은 /api/delete-item으로 삭제)
+    pub async fn delete_asset(&self, id: &str) -> Result<Asset, AssetError> {
+        self.assets
+            .write()
+            .await
+            .remove(id)
+            .ok_or_else(|| AssetError::NotFound(id.to_string()))
+    }
+
+    // 에셋 자막 교체 (에셋이 없으면 None)
+    pub async fn replace_subtitles(&self, id: &str, subtitles: Vec<SubtitleData>, actor: &str) -> Option<Asset> {
+        let mut assets = self.assets.write().await;
+        let asset = assets.get_mut(id)?;
+        asset.subtitles = subtitles;
+        asset.touch(actor);
+        Some(asset.clone())
+    }
+
+    // 에셋 YouTube 링크 교체 (에셋이 없으면 None)
+    pub async fn replace_youtube_links(&self, id: &str, youtube_links: Vec<YouTubeLink>, actor: &str) -> Option<Asset> {
+        let mut assets = self.assets.write().await;
+        let asset = assets.get_mut(id)?;
+        asset.youtube_links = youtube_links;
+        asset.touch(actor);
+        Some(asset.clone())
+    }
+
+    /// The asset's YouTube links with titles rebuilt from the thumbnail filenames.
+    /// Stored links are left unchanged; links whose filename yields no title keep theirs.
+    pub async fn regenerate_youtube_links(&self, id: &str) -> Option<Vec<YouTubeLink>> {
+        let asset = self.get(id).await?;
+        let links = asset
+            .youtube_links
+            .into_iter()
+            .map(|link| YouTubeLink {
+                title: heuristic_title(&link.thumbnail_file).or(link.title),
+                ..link
+            })
+            .collect();
+        Some(links)
+    }
+
+    // 에셋의 YouTube 링크를 동시에 확인 (에셋이 없으면 None)
+    pub async fn validate_youtube_links(&self, youtube: &YouTubeService, asset_id: &str) -> Option<YouTubeValidationReport> {
+        let asset = self.get(asset_id).await?;
+        let links = futures::future::join_all(asset.youtube_links.iter().map(|link| async move {
+            YouTubeLinkCheck {
+                youtube_url: link.youtube_url.clone(),
+                title: link.title.clone(),
+                status: youtube.check_link(&link.youtube_url).await,
+            }
+        }))
+        .await;
+
+        let broken = links
+            .iter()
+            .filter(|check| check.status == YouTubeLinkStatus::Unavailable)
+            .map(|check| check.youtube_url.clone())
+            .collect();
+        Some(YouTubeValidationReport { asset_id: asset.id, broken, links })
+    }
+
+    // 지정된 조건에 모두 맞는 에셋 (id 순 정렬)
+    pub async fn get_filtered_assets(&self, filter: &FilterParams) -> Vec<Asset> {
+        let assets = self.assets.read().await;
+        let mut result: Vec<Asset> = assets
+            .values()
+            .filter(|asset| filter.book_id.as_deref().is_none_or(|id| asset.book_id == id))
+            .filter(|asset| filter.has_video.is_none_or(|has_video| asset.youtube_links.is_empty() != has_video))
+            .filter(|asset| filter.created_after.is_none_or(|after| asset.created_at >= after))
+            .filter(|asset| filter.created_before.is_none_or(|before| asset.created_at <= before))
+            .cloned()
+            .collect();
+        result.sort_by(|a, b| a.id.cmp(&b.id));
+        result
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct YouTubeLinkCheck {
+    pub youtube_url: String,
+    pub title: Option<String>,
+    pub status: YouTubeLinkStatus,
+}
+
+/// Per-link oEmbed results for one asset; `broken` lists the unavailable URLs
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct YouTubeValidationReport {
+    pub asset_id: String,
+    pub broken: Vec<String>,
+    pub links: Vec<YouTubeLinkCheck>,
+}
+
+/// Reorders `links` so that position `i` holds `links[order[i]]`.
+/// Returns `None` unless `order` is a permutation of `0..links.len()`.
+pub fn reorder_links(links: &[YouTubeLink], order: &[usize]) -> Option<Vec<YouTubeLink>> {
+    if order.len() != links.len() {
+        return None;
+    }
+    let mut seen = vec![false; links.len()];
+    for &index in order {
+        if index >= links.len() || std::mem::replace(&mut seen[index], true) {
+            return None;
+        }
+    }
+    Some(order.iter().map(|&index| links[index].clone()).collect())
+}
+
+/// Result of comparing the recorded asset files with the files present in R2
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct FileCrossReference {
+    pub matched: Vec<String>,
+    pub missing_in_r2: Vec<String>,   // recorded on an asset, not found in R2
+    pub untracked_in_r2: Vec<String>, // in R2, not recorded on any asset
+}
+
+/// Compares the file keys recorded on `assets` with `r2_keys`. Results are sorted.
+pub fn cross_reference_files(assets: &[Asset], r2_keys: &[String]) -> FileCrossReference {
+    let recorded: std::collections::BTreeSet<&str> = assets
+        .iter()
+        .flat_map(|asset| asset.files.iter().map(String::as_str))
+        .collect();
+    let present: std::collections::BTreeSet<&str> = r2_keys.iter().map(String::as_str).collect();
+
+    FileCrossReference {
+        matched: recorded.intersection(&present).map(|key| key.to_string()).collect(),
+        missing_in_r2: recorded.difference(&present).map(|key| key.to_string()).collect(),
+        untracked_in_r2: present.difference(&recorded).map(|key| key.to_string()).collect(),
+    }
+}
+
+/// Top-level R2 folders and asset `book_id`s that have no counterpart on the other side
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct OrphanReport {
+    pub folders_without_assets: Vec<String>,
+    pub assets_without_folders: Vec<String>,
+}
+
+/// Compares R2 top-level folder names with asset `book_id`s. Results are sorted.
+pub fn find_orphans(r2_folders: &[String], assets: &[Asset]) -> OrphanReport {
+    let folders: std::collections::BTreeSet<&str> = r2_folders.iter().map(String::as_str).collect();
+    let book_ids: std::collections::BTreeSet<&str> = assets.iter().map(|asset| asset.book_id.as_str()).collect();
+
+    OrphanReport {
+        folders_without_assets: folders.difference(&book_ids).map(|id| id.to_string()).collect(),
+        assets_without_folders: book_ids.difference(&folders).map(|id| id.to_string()).collect(),
+    }
+}
+
+/// A JSON list file as read from R2
+#[derive(Debug, Clone, PartialEq)]
+pub enum R2JsonFile<T> {
+    Missing,
+    Unreadable(String),
+    Parsed(Vec<T>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonFileStatus {
+    InSync,
+    Missing,
+    Unreadable,
+    Differs,
+}
+
+/// One of an asset's JSON files in R2 compared with its metadata
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct JsonFileDiff {
+    pub file: String,
+    pub status: JsonFileStatus,
+    pub metadata_count: usize,
+    pub file_count: Option<usize>,
+    /// Positions where both lists have an entry and the entries differ
+    pub differing_entries: Vec<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Compares an asset list with the contents of its JSON file.
+/// A missing file is in sync with an empty list, since empty lists are never written.
+pub fn diff_json_file<T: PartialEq>(file: &str, metadata: &[T], on_r2: &R2JsonFile<T>) -> JsonFileDiff {
+    let mut diff = JsonFileDiff {
+        file: file.to_string(),
+        status: JsonFileStatus::InSync,
+        metadata_count: metadata.len(),
+        file_count: None,
+        differing_entries: Vec::new(),
+        error: None,
+    };
+    match on_r2 {
+        R2JsonFile::Missing if metadata.is_empty() => {}
+        R2JsonFile::Missing => diff.status = JsonFileStatus::Missing,
+        R2JsonFile::Unreadable(error) => {
+            diff.status = JsonFileStatus::Unreadable;
+            diff.error = Some(error.clone());
+        }
+        R2JsonFile::Parsed(entries) => {
+            diff.file_count = Some(entries.len());
+            diff.differing_entries = metadata
+                .iter()
+                .zip(entries)
+                .enumerate()
+                .filter(|(_, (stored, written))| stored != written)
+                .map(|(index, _)| index)
+                .collect();
+            if entries.len() != metadata.len() || !diff.differing_entries.is_empty() {
+                diff.status = JsonFileStatus::Differs;
+            }
+        }
+    }
+    diff
+}
+
+/// Drift between an asset's metadata and the JSON files written next to its media
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct AssetDiffReport {
+    pub asset_id: String,
+    pub in_sync: bool,
+    pub files: Vec<JsonFileDiff>,
+}
@@ -0,0 +1,58 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::models::audit_log::{AuditLog, AUDIT_RESULT_FAILURE, AUDIT_RESULT_SUCCESS};
+
+// 메모리에 유지할 최대 감사 로그 개수 (초과 시 오래된 항목부터 제거)
+const MAX_AUDIT_LOGS: usize = 10_000;
+
+#[derive(Clone, Default)]
+pub struct AuditService {
+    logs: Arc<RwLock<VecDeque<AuditLog>>>,
+}
+
+impl AuditService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn record(&self, user: &str, action: &str, target_key: &str, success: bool) -> AuditLog {
+        let entry = AuditLog {
+            id: Uuid::new_v4().to_string(),
+            user: user.to_string(),
+            action: action.to_string(),
+            target_key: target_key.to_string(),
+            timestamp: Utc::now(),
+            result: if success { AUDIT_RESULT_SUCCESS } else { AUDIT_RESULT_FAILURE }.to_string(),
+        };
+
+        tracing::info!(
+            target: "audit",
+            user = %entry.user,
+            action = %entry.action,
+            target_key = %entry.target_key,
+            result = %entry.result,
+            "audit event"
+        );
+
+        let mut logs = self.logs.write().await;
+        if logs.len() >= MAX_AUDIT_LOGS {
+            logs.pop_back();
+        }
+        logs.push_front(entry.clone());
+
+        entry
+    }
+
+    // 최신순 페이지 조회 (page는 1부터 시작)
+    pub async fn list(&self, page: usize, limit: usize) -> (Vec<AuditLog>, usize) {
+        let logs = self.logs.read().await;
+        let skip = page.saturating_sub(1) * limit;
+        let items = logs.iter().skip(skip).take(limit).cloned().collect();
+        (items, logs.len())
+    }
+}
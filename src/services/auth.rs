@@ -1,32 +1,142 @@
-use anyhow::{Ok, Result};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{Context, Ok, Result};
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use reqwest;
 
-use crate::{dto::auth::{ClaimsResponse, LoginRequest, LoginResponse}, models::user::AdminUser};
+use crate::{config::{AuthConfig, DevUser}, utils::constant_time_eq, dto::auth::{ClaimsResponse, LoginRequest, LoginResponse}, models::user::AdminUser, services::{revocation::RevocationList, user::UserService}};
+
+/// Used when neither `JWT_SECRET_FILE` nor `JWT_SECRET` is set; only acceptable in debug
+pub const INSECURE_DEFAULT_JWT_SECRET: &str = "default-secret-key";
+
+/// Secret from the file at `secret_file` if given (trailing whitespace trimmed), else
+/// `env_secret`, else the insecure default
+pub fn jwt_secret_from(secret_file: Option<&Path>, env_secret: Option<&str>) -> Result<String> {
+    if let Some(path) = secret_file {
+        let secret = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read JWT_SECRET_FILE '{}'", path.display()))?;
+        let secret = secret.trim_end();
+        if secret.is_empty() {
+            anyhow::bail!("JWT_SECRET_FILE '{}' is empty", path.display());
+        }
+        return Ok(secret.to_string());
+    }
+
+    // 빈 JWT_SECRET은 설정하지 않은 것으로 취급
+    Ok(env_secret
+        .filter(|secret| !secret.is_empty())
+        .unwrap_or(INSECURE_DEFAULT_JWT_SECRET)
+        .to_string())
+}
+
+pub fn load_jwt_secret() -> Result<String> {
+    let secret_file = std::env::var_os("JWT_SECRET_FILE").map(PathBuf::from);
+    jwt_secret_from(secret_file.as_deref(), std::env::var("JWT_SECRET").ok().as_deref())
+}
+
+/// Production unless `app.debug` is on, and always when `APP_ENV` is prod/production
+pub fn is_production(debug: bool, app_env: Option<&str>) -> bool {
+    !debug || matches!(app_env.map(str::to_lowercase).as_deref(), Some("prod" | "production"))
+}
+
+/// Refuses an unset or default secret unless `debug` is set; in debug it only warns
+pub fn check_jwt_secret(secret: &str, debug: bool) -> Result<()> {
+    if secret != INSECURE_DEFAULT_JWT_SECRET {
+        return Ok(());
+    }
+    if !debug {
+        anyhow::bail!(
+            "JWT secret is unset or the insecure default; set JWT_SECRET or JWT_SECRET_FILE \
+             (required in production: app.debug=false or APP_ENV=prod)"
+        );
+    }
+    tracing::warn!("Using the insecure default JWT secret (debug mode); set JWT_SECRET or JWT_SECRET_FILE");
+    Ok(())
+}
 
+// 시크릿 파일은 프로세스당 한 번만 읽음 (시작 시 ensure_jwt_secret으로 먼저 검증)
+fn jwt_secret() -> String {
+    static SECRET: OnceLock<String> = OnceLock::new();
+    SECRET
+        .get_or_init(|| {
+            load_jwt_secret().unwrap_or_else(|e| {
+                tracing::error!("{:#}", e);
+                INSECURE_DEFAULT_JWT_SECRET.to_string()
+            })
+        })
+        .clone()
+}
+
+/// Startup check: loads the JWT secret and applies `check_jwt_secret`
+pub fn ensure_jwt_secret(production: bool) -> Result<()> {
+    let secret = load_jwt_secret()?;
+    check_jwt_secret(&secret, !production)
+}
 
 pub struct AuthService {
     jwt_secret: String,
     client: reqwest::Client,
+    login_url: String,
+    user_service: Option<Arc<UserService>>,
+    dev_mode: bool,
+    dev_users: Vec<DevUser>,
+    // 이 서비스로 로그아웃한 토큰 (AppState의 AuthService 하나를 모든 요청이 공유)
+    revocation_list: RevocationList,
+}
+
+impl Default for AuthService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AuthService {
     pub fn new() -> Self {
-        let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "default-secret-key".to_string());
-        let client = reqwest::Client::new();
-        Self { jwt_secret, client }
+        Self::from_config(&AuthConfig::default())
+    }
+
+    pub fn from_config(config: &AuthConfig) -> Self {
+        let jwt_secret = jwt_secret();
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(config.timeout_seconds))
+            .build()
+            .expect("Failed to build HTTP client");
+        let dev_mode = config.dev_mode || std::env::var("DEV_MODE").is_ok_and(|value| value == "true");
+        Self {
+            jwt_secret,
+            client,
+            login_url: config.login_url.clone(),
+            user_service: None,
+            dev_mode,
+            dev_users: config.dev_users.clone(),
+            revocation_list: RevocationList::new(),
+        }
+    }
+
+    pub fn with_user_service(mut self, user_service: Arc<UserService>) -> Self {
+        self.user_service = Some(user_service);
+        self
     }
 
     pub fn generate_admin_token(&self, user: &AdminUser) -> Result<String> {
+        self.generate_admin_token_with_ttl(user, Duration::hours(24))
+    }
+
+    pub fn generate_admin_token_with_ttl(&self, user: &AdminUser, ttl: Duration) -> Result<String> {
         let now = Utc::now();
-        let exp = now + Duration::hours(24);
+        let exp = now + ttl;
 
         let claims = ClaimsResponse {
             username: user.account.clone(),
             role: user.role.clone(),
+            account_id: user.account_id,
+            agency_id: user.agency_id,
+            academy_id: user.academy_id,
             exp: exp.timestamp() as usize,
             iat: now.timestamp() as usize,
+            jti: uuid::Uuid::new_v4().to_string(),
         };
 
         let token = encode( &Header::default(), &claims, &EncodingKey::from_secret(self.jwt_secret.as_ref()))?;
@@ -34,6 +144,15 @@ impl AuthService {
         Ok(token)
     }
 
+    /// Revokes `claims`' token until it expires; later `validate_token` calls reject it
+    pub fn revoke_token(&self, claims: &ClaimsResponse) -> Result<()> {
+        if claims.jti.is_empty() {
+            anyhow::bail!("token has no jti and cannot be revoked");
+        }
+        self.revocation_list.revoke(&claims.jti, claims.exp as i64);
+        Ok(())
+    }
+
     pub fn validate_token(&self, token: &str) -> Result<ClaimsResponse> {
         let token_data = decode::<ClaimsResponse>(
             token,
@@ -41,7 +160,15 @@ impl AuthService {
             &Validation::new(Algorithm::HS256),
         )?;
 
-        Ok(token_data.claims)
+        let claims = token_data.claims;
+        // jti 없는 토큰은 폐기할 수 없으므로 받지 않음 (도입 전 발급된 토큰은 다시 로그인)
+        if claims.jti.is_empty() {
+            anyhow::bail!("token has no jti");
+        }
+        if self.revocation_list.is_revoked(&claims.jti) {
+            anyhow::bail!("token has been revoked");
+        }
+        Ok(claims)
     }
 
     pub async fn authenticate_user(&self, account: &str, password: &str) -> Result<Option<AdminUser>> {
@@ -50,6 +177,13 @@ impl AuthService {
             return Ok(Some(dev_user));
         }
 
+        // Then local users
+        if let Some(user_service) = &self.user_service {
+            if let Some(local_user) = user_service.authenticate(account, password).await {
+                return Ok(Some(local_user));
+            }
+        }
+
         // Fall back to external API authentication
         let login_request = LoginRequest {
             account: account.to_string(),
@@ -57,7 +191,7 @@ impl AuthService {
         };
 
         let response = self.client
-            .post("https://dev-admin.reengki.com/api/applogin")
+            .post(&self.login_url)
             .json(&login_request)
             .send()
             .await?;
@@ -74,52 +208,27 @@ impl AuthService {
     }
 
     fn try_dev_authentication(&self, account: &str, password: &str) -> Option<AdminUser> {
-        let dev_mode = std::env::var("DEV_MODE").unwrap_or_else(|_| "false".to_string()) == "true";
-        
-        if !dev_mode {
+        if !self.dev_mode {
             return None;
         }
 
-        // Admin account
-        if account == std::env::var("DEV_ADMIN_ACCOUNT").unwrap_or_else(|_| "admin".to_string()) 
-            && password == std::env::var("DEV_ADMIN_PASSWORD").unwrap_or_else(|_| "admin123".to_string()) {
-            return Some(AdminUser {
-                account_id: std::env::var("DEV_ADMIN_ACCOUNT_ID").unwrap_or_else(|_| "1".to_string()).parse().unwrap_or(1),
-                account: account.to_string(),
-                role: std::env::var("DEV_ADMIN_ROLE").unwrap_or_else(|_| "HEAD_OFFICE".to_string()),
-                agency_id: std::env::var("DEV_ADMIN_AGENCY_ID").unwrap_or_else(|_| "1".to_string()).parse().unwrap_or(1),
-                academy_id: std::env::var("DEV_ADMIN_ACADEMY_ID").unwrap_or_else(|_| "1".to_string()).parse().unwrap_or(1),
-                is_active: std::env::var("DEV_ADMIN_IS_ACTIVE").unwrap_or_else(|_| "true".to_string()) == "true",
-            });
-        }
-
-        // Director account
-        if account == std::env::var("DEV_DIRECTOR_ACCOUNT").unwrap_or_else(|_| "director".to_string()) 
-            && password == std::env::var("DEV_DIRECTOR_PASSWORD").unwrap_or_else(|_| "director123".to_string()) {
-            return Some(AdminUser {
-                account_id: std::env::var("DEV_DIRECTOR_ACCOUNT_ID").unwrap_or_else(|_| "2".to_string()).parse().unwrap_or(2),
-                account: account.to_string(),
-                role: std::env::var("DEV_DIRECTOR_ROLE").unwrap_or_else(|_| "DIRECTOR".to_string()),
-                agency_id: std::env::var("DEV_DIRECTOR_AGENCY_ID").unwrap_or_else(|_| "2".to_string()).parse().unwrap_or(2),
-                academy_id: std::env::var("DEV_DIRECTOR_ACADEMY_ID").unwrap_or_else(|_| "2".to_string()).parse().unwrap_or(2),
-                is_active: std::env::var("DEV_DIRECTOR_IS_ACTIVE").unwrap_or_else(|_| "true".to_string()) == "true",
-            });
-        }
-
-        // Regional Manager account
-        if account == std::env::var("DEV_REGIONAL_ACCOUNT").unwrap_or_else(|_| "regional".to_string()) 
-            && password == std::env::var("DEV_REGIONAL_PASSWORD").unwrap_or_else(|_| "regional123".to_string()) {
-            return Some(AdminUser {
-                account_id: std::env::var("DEV_REGIONAL_ACCOUNT_ID").unwrap_or_else(|_| "3".to_string()).parse().unwrap_or(3),
-                account: account.to_string(),
-                role: std::env::var("DEV_REGIONAL_ROLE").unwrap_or_else(|_| "REGIONAL_MANAGER".to_string()),
-                agency_id: std::env::var("DEV_REGIONAL_AGENCY_ID").unwrap_or_else(|_| "3".to_string()).parse().unwrap_or(3),
-                academy_id: std::env::var("DEV_REGIONAL_ACADEMY_ID").unwrap_or_else(|_| "3".to_string()).parse().unwrap_or(3),
-                is_active: std::env::var("DEV_REGIONAL_IS_ACTIVE").unwrap_or_else(|_| "true".to_string()) == "true",
-            });
-        }
-
-        None
+        self.dev_users
+            .iter()
+            .find(|dev_user| credentials_match(account, password, &dev_user.account, &dev_user.password))
+            .map(|dev_user| AdminUser {
+                account_id: dev_user.account_id,
+                account: dev_user.account.clone(),
+                role: dev_user.role.clone(),
+                agency_id: dev_user.agency_id,
+                academy_id: dev_user.academy_id,
+                is_active: dev_user.is_active,
+            })
     }
+}
 
-}
\ No newline at end of file
+// 계정/비밀번호를 모두 상수 시간으로 비교 (첫 번째 불일치에서 멈추지 않음)
+fn credentials_match(account: &str, password: &str, expected_account: &str, expected_password: &str) -> bool {
+    let account_ok = constant_time_eq(account.as_bytes(), expected_account.as_bytes());
+    let password_ok = constant_time_eq(password.as_bytes(), expected_password.as_bytes());
+    account_ok & password_ok
+}
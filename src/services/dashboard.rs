@@ -1,11 +1,26 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
+use sysinfo::System;
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::services::asset::AssetService;
+use crate::services::audit::AuditService;
+use crate::utils::build_info::BuildInfo;
+
+// 대시보드에 보여줄 최근 활동(감사 로그) 개수
+pub const RECENT_ACTIVITY_LIMIT: usize = 10;
+
+#[derive(Debug, Serialize)]
 pub struct DashboardStats {
-    pub total_assets: u32,
+    pub total_assets: usize,
     pub active_users: u32,
     pub recent_activities: Vec<ActivityLog>,
+    pub uptime: String,
+    pub uptime_seconds: u64,
+    pub build: BuildInfo,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,48 +32,125 @@ pub struct ActivityLog {
     pub details: Option<String>,
 }
 
-pub struct DashboardService;
+pub struct DashboardService {
+    asset_service: Arc<AssetService>,
+    audit_service: Arc<AuditService>,
+    /// Same flag as `AppState::ready`
+    ready: Arc<AtomicBool>,
+    started_at: Instant,
+    build: BuildInfo,
+    // CPU 사용률은 직전 갱신과의 차이로 계산되므로 같은 System을 계속 사용
+    system: Mutex<System>,
+}
 
 impl DashboardService {
-    pub fn new() -> Self {
-        Self
+    pub fn new(
+        asset_service: Arc<AssetService>,
+        audit_service: Arc<AuditService>,
+        ready: Arc<AtomicBool>,
+        started_at: Instant,
+        build: BuildInfo,
+    ) -> Self {
+        Self { asset_service, audit_service, ready, started_at, build, system: Mutex::new(System::new()) }
     }
 
     pub async fn get_dashboard_stats(&self) -> Result<DashboardStats> {
-        // For now, return mock data
-        // In a real implementation, this would query the database
-        let stats = DashboardStats {
-            total_assets: 0, // This would be fetched from the database
-            active_users: 1, // This would be fetched from active sessions
-            recent_activities: vec![
-                ActivityLog {
-                    id: "1".to_string(),
-                    user: "admin".to_string(),
-                    action: "로그인".to_string(),
-                    timestamp: chrono::Utc::now(),
-                    details: Some("관리자 시스템 로그인".to_string()),
-                }
-            ],
-        };
+        let (recent, _) = self.audit_service.list(1, RECENT_ACTIVITY_LIMIT).await;
+        let recent_activities = recent
+            .into_iter()
+            .map(|entry| ActivityLog {
+                details: Some(format!("{} ({})", entry.target_key, entry.result)),
+                id: entry.id,
+                user: entry.user,
+                action: entry.action,
+                timestamp: entry.timestamp,
+            })
+            .collect();
+        let uptime = self.started_at.elapsed();
 
-        Ok(stats)
+        Ok(DashboardStats {
+            total_assets: self.asset_service.count().await,
+            active_users: 1, // This would be fetched from active sessions
+            recent_activities,
+            uptime: format_uptime(uptime),
+            uptime_seconds: uptime.as_secs(),
+            build: self.build.clone(),
+        })
     }
 
     pub async fn get_system_health(&self) -> Result<SystemHealth> {
-        // Mock system health data
+        let usage = self.process_usage()?;
+        let memory_usage = if usage.total_memory > 0 {
+            usage.memory_bytes as f64 / usage.total_memory as f64 * 100.0
+        } else {
+            0.0
+        };
+        let status = if self.ready.load(Ordering::Acquire) { "healthy" } else { "starting" };
+
         Ok(SystemHealth {
-            status: "healthy".to_string(),
-            uptime: "1 day, 2 hours".to_string(),
-            memory_usage: 45.2,
-            cpu_usage: 12.5,
+            status: status.to_string(),
+            uptime: format_uptime(self.started_at.elapsed()),
+            memory_usage,
+            memory_bytes: usage.memory_bytes,
+            cpu_usage: f64::from(usage.cpu_usage),
         })
     }
+
+    // 현재 프로세스의 상주 메모리와 CPU 사용률, 호출할 때마다 갱신
+    fn process_usage(&self) -> Result<ProcessUsage> {
+        let pid = sysinfo::get_current_pid().map_err(|e| anyhow::anyhow!("Failed to get current pid: {}", e))?;
+        let mut system = self.system.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        system.refresh_memory();
+        if !system.refresh_process(pid) {
+            anyhow::bail!("Failed to read process {} metrics", pid);
+        }
+        let process = system.process(pid).ok_or_else(|| anyhow::anyhow!("Process {} not found", pid))?;
+        Ok(ProcessUsage {
+            memory_bytes: process.memory(),
+            total_memory: system.total_memory(),
+            cpu_usage: process.cpu_usage(),
+        })
+    }
+}
+
+struct ProcessUsage {
+    memory_bytes: u64,
+    total_memory: u64,
+    cpu_usage: f32,
+}
+
+// 큰 단위 두 개까지: "1 day, 2 hours", "3 hours, 5 minutes", "42 seconds"
+pub fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    let units = [
+        (secs / 86_400, "day"),
+        (secs / 3_600 % 24, "hour"),
+        (secs / 60 % 60, "minute"),
+        (secs % 60, "second"),
+    ];
+    let parts: Vec<String> = units
+        .iter()
+        .skip_while(|(count, _)| *count == 0)
+        .take(2)
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, unit)| format!("{} {}{}", count, unit, if *count == 1 { "" } else { "s" }))
+        .collect();
+    if parts.is_empty() {
+        "0 seconds".to_string()
+    } else {
+        parts.join(", ")
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SystemHealth {
+    /// "healthy" once startup tasks finished, "starting" before
     pub status: String,
     pub uptime: String,
+    /// Resident memory as a percentage of total system memory
     pub memory_usage: f64,
+    /// Resident memory of this process
+    pub memory_bytes: u64,
+    /// Process CPU percentage since the previous call (0 on the first call; can exceed 100 on multiple cores)
     pub cpu_usage: f64,
-}
\ No newline at end of file
+}
@@ -1,45 +1,301 @@
 use crate::dto::file::{
-    DeleteFileRequest, DeleteFileResponse, FileUploadResponse,
+    DeleteFileRequest, DeleteFileResponse, FileUploadResponse, UploadedFile,
 };
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
 use axum::body::Bytes;
 use reqwest::{multipart, Client};
-use std::sync::Arc;
+use crate::config::HttpClientConfig;
+use crate::utils::spool::SpooledFile;
+use crate::utils::telemetry::inject_trace_context;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
-use std::collections::HashMap;
+use tokio::sync::{broadcast, Mutex, OwnedMutexGuard, RwLock};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use futures::{stream, StreamExt, TryStreamExt};
+use thiserror::Error;
 
 #[derive(Clone)]
 pub struct FileService {
     client: Arc<Client>,
+    http_client: HttpClientConfig,
     base_url: String,
+    upload_url: String,
+    folder_files_url: String,
+    parallel_listing: Option<ParallelListing>,
     bucket: String,
+    // 기본 버킷 외에 요청에서 지정할 수 있는 버킷
+    allowed_buckets: Vec<String>,
+    default_category: String,
+    cache_ttl: Duration,
+    // 전체 카테고리 캐시 항목 수 상한 (None이면 무제한)
+    cache_max_items: Option<usize>,
+    // 캐시된 목록을 압축해서 보관 (대용량 버킷에서 메모리 절약, 조회 시 CPU 사용)
+    compress_cache: bool,
     // 카테고리별 전체 데이터 메모리 캐시
     all_files_cache: Arc<RwLock<HashMap<String, AllFilesCache>>>,
+    // 카테고리별 마지막 강제 새로고침 시각 (fresh=true 남용 방지)
+    last_forced_refresh: Arc<RwLock<HashMap<String, Instant>>>,
+    // 전체 목록 캐시 적중/미스 카운터 (TTL 조정용)
+    cache_metrics: Arc<CacheMetrics>,
+    // 업로드/삭제로 폴더 내용이 바뀌면 발행 (SSE 구독자용)
+    folder_events: broadcast::Sender<FolderChangeEvent>,
+    // R2 전체 경로별 업로드 잠금 (같은 키로의 동시 업로드를 직렬화)
+    upload_locks: Arc<std::sync::Mutex<HashMap<String, Arc<Mutex<()>>>>>,
+}
+
+/// A request named a bucket that is neither the configured bucket nor in `allowed_buckets`
+#[derive(Debug, Error)]
+#[error("Bucket not allowed: {0}")]
+pub struct BucketNotAllowed(pub String);
+
+/// An upload with `overwrite` off found its key already present on R2
+#[derive(Debug, Clone, Error)]
+#[error("File already exists: {key}")]
+pub struct KeyExists {
+    pub key: String,
+    pub url: String,
+}
+
+/// R2 answered successfully but with a body that is not JSON (e.g. an HTML error page)
+#[derive(Debug, Error)]
+#[error("R2 returned a non-JSON response (content-type: '{content_type}'): {preview}")]
+pub struct NonJsonResponse {
+    pub content_type: String,
+    /// Start of the body, whitespace collapsed
+    pub preview: String,
+}
+
+// 오류 메시지에 넣을 본문 미리보기 길이 (문자 수)
+const BODY_PREVIEW_CHARS: usize = 200;
+
+fn body_preview(body: &str) -> String {
+    let collapsed = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    match collapsed.char_indices().nth(BODY_PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}...", &collapsed[..end]),
+        None => collapsed,
+    }
+}
+
+/// Published whenever an upload or delete changes the contents under `path`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderChangeEvent {
+    pub path: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+// 느린 구독자가 놓칠 수 있는 이벤트 버퍼 크기
+const FOLDER_EVENT_CAPACITY: usize = 256;
+
+// 같은 카테고리의 강제 새로고침 최소 간격
+const FORCED_REFRESH_MIN_INTERVAL: Duration = Duration::from_secs(10);
+
+// 전체 목록 캐시 기본 TTL
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(1800);
+
+// 갱신 실패 후 만료된 캐시를 제공하면서 R2 재시도를 미루는 간격
+const STALE_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+// 목록 조회 한 번에 받을 최대 페이지 수 (페이지네이션을 무시하는 Worker 대비)
+const MAX_LISTING_PAGES: usize = 10_000;
+
+// 압축된 목록을 푼 결과를 재사용하는 시간 (한 요청 안의 여러 조회가 한 번만 압축 해제)
+const DECODED_LISTING_TTL: Duration = Duration::from_secs(5);
+
+/// offset/limit 페이지 병렬 조회 설정 (R2 Worker가 offset/limit를 지원할 때만 사용)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParallelListing {
+    pub page_size: usize,
+    pub concurrency: usize,
+}
+
+// 캐시된 목록: 그대로 보관하거나, 직렬화한 JSON을 gzip으로 압축해 보관
+#[derive(Debug)]
+enum CachedListing {
+    Plain(R2WorkerFolderResponse),
+    Compressed {
+        gzip_json: Vec<u8>,
+        len: usize,
+        // 최근에 푼 목록 (DECODED_LISTING_TTL 동안만 보관)
+        decoded: DecodedSlot,
+    },
+}
+
+type DecodedSlot = Arc<std::sync::Mutex<Option<DecodedListing>>>;
+
+#[derive(Debug)]
+struct DecodedListing {
+    decoded_at: Instant,
+    data: Arc<R2WorkerFolderResponse>,
+}
+
+impl DecodedListing {
+    fn new(data: Arc<R2WorkerFolderResponse>) -> Self {
+        Self { decoded_at: Instant::now(), data }
+    }
+
+    fn is_fresh(&self) -> bool {
+        self.decoded_at.elapsed() < DECODED_LISTING_TTL
+    }
+
+    // 압축 해제본을 보관하고, 재사용 시간이 지나면 버려서 압축한 만큼의 메모리를 되찾음
+    fn keep(slot: &DecodedSlot, data: Arc<R2WorkerFolderResponse>) {
+        *slot.lock().unwrap() = Some(Self::new(data));
+        let slot = Arc::downgrade(slot);
+        tokio::spawn(async move {
+            tokio::time::sleep(DECODED_LISTING_TTL).await;
+            let Some(slot) = slot.upgrade() else { return };
+            let mut decoded = slot.lock().unwrap();
+            if decoded.as_ref().is_some_and(|recent| !recent.is_fresh()) {
+                *decoded = None;
+            }
+        });
+    }
 }
 
-#[derive(Debug, Clone)]
+impl CachedListing {
+    fn new(data: R2WorkerFolderResponse, compress: bool) -> Self {
+        if !compress {
+            return Self::Plain(data);
+        }
+        match compress_listing(&data) {
+            // 방금 받은 목록은 바로 다시 읽히므로 푼 상태로도 잠시 보관
+            Ok(gzip_json) => {
+                let decoded = DecodedSlot::default();
+                let len = data.len();
+                DecodedListing::keep(&decoded, Arc::new(data));
+                Self::Compressed { gzip_json, len, decoded }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to compress cached listing, storing it uncompressed: {}", e);
+                Self::Plain(data)
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Plain(data) => data.len(),
+            Self::Compressed { len, .. } => *len,
+        }
+    }
+
+    fn to_vec(&self, metrics: &CacheMetrics) -> Result<R2WorkerFolderResponse> {
+        match self {
+            Self::Plain(data) => Ok(data.clone()),
+            Self::Compressed { gzip_json, len, decoded } => {
+                if let Some(recent) = decoded.lock().unwrap().as_ref().filter(|recent| recent.is_fresh()) {
+                    return Ok(recent.data.as_ref().clone());
+                }
+
+                use std::io::Read;
+                let mut json = Vec::with_capacity(gzip_json.len() * 4);
+                flate2::read::GzDecoder::new(gzip_json.as_slice()).read_to_end(&mut json)?;
+                let data: R2WorkerFolderResponse = serde_json::from_slice(&json)?;
+                debug_assert_eq!(data.len(), *len);
+                metrics.decompressions.fetch_add(1, Ordering::Relaxed);
+
+                let data = Arc::new(data);
+                DecodedListing::keep(decoded, data.clone());
+                Ok(data.as_ref().clone())
+            }
+        }
+    }
+}
+
+// to_writer로 인코더에 직접 쓰면 작은 write마다 압축이 돌아 매우 느림
+fn compress_listing(data: &R2WorkerFolderResponse) -> Result<Vec<u8>> {
+    use std::io::Write;
+    let json = serde_json::to_vec(data)?;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    encoder.write_all(&json)?;
+    Ok(encoder.finish()?)
+}
+
+#[derive(Debug, Default)]
+struct CacheMetrics {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    background_refreshes: AtomicU64,
+    decompressions: AtomicU64,
+}
+
+/// All-files cache counters since startup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheCounters {
+    /// Lookups answered from the cache (including stale data served after a failed refresh)
+    pub hits: u64,
+    /// Lookups that had to load the listing from R2
+    pub misses: u64,
+    /// Background refreshes started for entries close to expiry
+    pub background_refreshes: u64,
+    /// Times a compressed listing was decompressed (0 unless `compress_cache` is on)
+    pub decompressions: u64,
+}
+
+impl CacheCounters {
+    /// `hits / (hits + misses)`, or `None` before the first lookup
+    pub fn hit_ratio(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+#[derive(Debug)]
 struct AllFilesCache {
-    data: R2WorkerFolderResponse,
+    data: CachedListing,
     created_at: Instant,
     ttl: Duration,
+    // 만료 후 갱신이 실패한 시각 (이전 데이터를 계속 제공 중)
+    refresh_failed_at: Option<Instant>,
+    // 마지막 조회 시각 (항목 수 상한 초과 시 LRU 제거 기준, 읽기 잠금 중에도 갱신)
+    last_accessed: std::sync::Mutex<Instant>,
 }
 
 impl AllFilesCache {
+    fn new(data: CachedListing, ttl: Duration) -> Self {
+        Self {
+            data,
+            created_at: Instant::now(),
+            ttl,
+            refresh_failed_at: None,
+            last_accessed: std::sync::Mutex::new(Instant::now()),
+        }
+    }
+
+    fn touch(&self) {
+        *self.last_accessed.lock().unwrap() = Instant::now();
+    }
+
+    fn last_accessed(&self) -> Instant {
+        *self.last_accessed.lock().unwrap()
+    }
+
     fn is_expired(&self) -> bool {
         self.created_at.elapsed() > self.ttl
     }
+
+    // 최근 갱신 실패 후 재시도 간격이 지나기 전이면 만료된 데이터를 그대로 사용
+    fn is_serving_stale(&self) -> bool {
+        self.is_expired() && self.refresh_failed_at.is_some_and(|failed| failed.elapsed() < STALE_RETRY_INTERVAL)
+    }
 }
 
 impl FileService {
     pub fn new(base_url: String, bucket: String) -> Self {
+        Self::from_config(base_url, bucket, &HttpClientConfig::default())
+    }
+
+    pub fn from_config(base_url: String, bucket: String, http_client: &HttpClientConfig) -> Self {
         // Create client with optimized settings for better performance
         let client = Client::builder()
-            .timeout(Duration::from_secs(600)) // 10 minutes timeout for large files
-            .connect_timeout(Duration::from_secs(10)) // Reduced connection timeout
-            .pool_max_idle_per_host(20) // Increase connection pool size
-            .pool_idle_timeout(Duration::from_secs(90)) // Keep connections alive longer
+            .timeout(Duration::from_secs(http_client.timeout_seconds))
+            .connect_timeout(Duration::from_secs(http_client.connect_timeout_seconds))
+            .pool_max_idle_per_host(http_client.pool_max_idle_per_host)
+            .pool_idle_timeout(Duration::from_secs(http_client.pool_idle_timeout_seconds))
             .tcp_nodelay(true) // Disable Nagle's algorithm for lower latency
             .danger_accept_invalid_certs(true) // Accept self-signed certificates for development
             // gzip and brotli are enabled by default in reqwest
@@ -48,70 +304,146 @@ impl FileService {
             
         Self {
             client: Arc::new(client),
+            http_client: http_client.clone(),
             base_url,
+            upload_url: "https://media-api.reengki.com/upload".to_string(),
+            folder_files_url: "https://assets.reengkigo.com/folder-files".to_string(),
+            parallel_listing: None,
             bucket,
+            allowed_buckets: Vec::new(),
+            default_category: "reengkigo".to_string(),
+            cache_ttl: DEFAULT_CACHE_TTL,
+            cache_max_items: None,
+            compress_cache: false,
             all_files_cache: Arc::new(RwLock::new(HashMap::new())),
+            cache_metrics: Arc::new(CacheMetrics::default()),
+            last_forced_refresh: Arc::new(RwLock::new(HashMap::new())),
+            folder_events: broadcast::channel(FOLDER_EVENT_CAPACITY).0,
+            upload_locks: Arc::new(std::sync::Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn with_upload_url(mut self, upload_url: String) -> Self {
+        self.upload_url = upload_url;
+        self
+    }
+
+    pub fn http_client_config(&self) -> &HttpClientConfig {
+        &self.http_client
+    }
+
+    // 전체 파일 목록(folder-files) API 주소 설정
+    pub fn with_folder_files_url(mut self, folder_files_url: String) -> Self {
+        self.folder_files_url = folder_files_url;
+        self
+    }
+
+    // 카테고리를 지정하지 않은 요청에 사용할 R2 카테고리
+    pub fn with_default_category(mut self, default_category: String) -> Self {
+        self.default_category = default_category;
+        self
+    }
+
+    // 전체 목록 캐시 TTL (기본 30분)
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    // 모든 카테고리를 합친 캐시 항목 수 상한; 넘으면 가장 오래 조회되지 않은 카테고리부터 제거
+    pub fn with_cache_max_items(mut self, cache_max_items: Option<usize>) -> Self {
+        self.cache_max_items = cache_max_items;
+        self
+    }
+
+    pub fn with_cache_compression(mut self, compress_cache: bool) -> Self {
+        self.compress_cache = compress_cache;
+        self
+    }
+
+    pub fn with_allowed_buckets(mut self, allowed_buckets: Vec<String>) -> Self {
+        self.allowed_buckets = allowed_buckets;
+        self
+    }
+
+    // 요청에서 지정한 버킷 검증 (비어 있으면 기본 버킷 사용)
+    pub fn requested_bucket<'a>(&self, bucket: &'a str) -> Result<Option<&'a str>, BucketNotAllowed> {
+        let bucket = bucket.trim();
+        if bucket.is_empty() {
+            Ok(None)
+        } else if bucket == self.bucket || self.allowed_buckets.iter().any(|allowed| allowed == bucket) {
+            Ok(Some(bucket))
+        } else {
+            Err(BucketNotAllowed(bucket.to_string()))
         }
     }
 
+    pub fn default_category(&self) -> &str {
+        &self.default_category
+    }
+
+    // 요청에 카테고리가 없으면 기본 카테고리 사용
+    pub fn category_or_default(&self, category: Option<String>) -> String {
+        category
+            .filter(|category| !category.is_empty())
+            .unwrap_or_else(|| self.default_category.clone())
+    }
+
+    // offset/limit 병렬 페이지 조회 사용 (None이면 nextCursor 순차 조회)
+    pub fn with_parallel_listing(mut self, parallel_listing: Option<ParallelListing>) -> Self {
+        self.parallel_listing = parallel_listing.map(|listing| ParallelListing {
+            page_size: listing.page_size.max(1),
+            concurrency: listing.concurrency.max(1),
+        });
+        self
+    }
+
     pub async fn upload_file(
         &self,
         files: Vec<(String, Bytes)>,
         bucket: Option<&str>,
         base_path: &str,
         category: Option<&str>,
+        overwrite: bool,
+    ) -> Result<FileUploadResponse> {
+        let files = files.into_iter().map(|(filename, bytes)| (filename, SpooledFile::from(bytes))).collect();
+        self.upload_spooled_files(files, bucket, base_path, category, overwrite).await
+    }
+
+    /// Same as `upload_file`, but disk-spilled bodies are streamed instead of loaded into memory.
+    /// If any file fails, the files already uploaded by this call are unlinked again.
+    /// With `overwrite` off, a key that already exists on R2 fails with [`KeyExists`].
+    #[tracing::instrument(skip(self, files), fields(file_count = files.len()))]
+    pub async fn upload_spooled_files(
+        &self,
+        files: Vec<(String, SpooledFile)>,
+        bucket: Option<&str>,
+        base_path: &str,
+        category: Option<&str>,
+        overwrite: bool,
     ) -> Result<FileUploadResponse> {
-        let url = "https://media-api.reengki.com/upload".to_string();
-        let bucket_name = bucket.unwrap_or(&self.bucket);
-        
         let file_count = files.len();
         tracing::info!("Starting file upload to {}: {} files", base_path, file_count);
         
         let mut all_uploaded = Vec::new();
+        let mut uploaded_keys = Vec::new();
         
         // Upload each file individually with complete fullpath
-        for (filename, bytes) in files {
+        for (filename, file) in files {
             let full_path = format!("{}{}", base_path, filename);
-            tracing::info!("Uploading file with fullpath: {}", full_path);
-            
-            let mut form = multipart::Form::new()
-                .text("bucket", bucket_name.to_string())
-                .text("fullpath", full_path.clone());
-            
-            // Add category if provided
-            if let Some(cat) = category {
-                form = form.text("category", cat.to_string());
-            }
-
-            let part = multipart::Part::bytes(bytes.to_vec()).file_name(filename.clone());
-            form = form.part("file", part);
-            
-            let response = self.client
-                .post(&url)
-                .multipart(form)
-                .send()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to send upload request for {}: {}", filename, e);
-                    anyhow::anyhow!("Upload request failed for {}: {}", filename, e)
-                })?;
-
-            if response.status().is_success() {
-                let mut result = response.json::<FileUploadResponse>().await?;
-                
-                // Map API fields for backward compatibility
-                for uploaded_file in &mut result.uploaded {
-                    uploaded_file.filename = uploaded_file.original_file.clone();
-                    uploaded_file.url = format!("http://localhost:5001/assets/{}", uploaded_file.file);
+            match self.upload_one(bucket, &full_path, filename, file, category, overwrite).await {
+                Ok(uploaded) => {
+                    all_uploaded.extend(uploaded);
+                    uploaded_keys.push(full_path);
+                }
+                Err(e) => {
+                    // 앞서 올라간 파일이 고아로 남지 않도록 되돌림
+                    if !uploaded_keys.is_empty() {
+                        tracing::warn!("Rolling back {} uploaded file(s) under {} after failure", uploaded_keys.len(), base_path);
+                        self.unlink_files(bucket, &uploaded_keys).await;
+                    }
+                    return Err(e);
                 }
-                
-                all_uploaded.extend(result.uploaded);
-                tracing::info!("Successfully uploaded file: {}", filename);
-            } else {
-                let status = response.status();
-                let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-                tracing::error!("Upload failed for {}: {} - {}", filename, status, error_text);
-                anyhow::bail!("Failed to upload file {}: {} - {}", filename, status, error_text);
             }
         }
         
@@ -126,6 +458,95 @@ impl FileService {
     }
 
 
+    async fn upload_one(
+        &self,
+        bucket: Option<&str>,
+        full_path: &str,
+        filename: String,
+        file: SpooledFile,
+        category: Option<&str>,
+        overwrite: bool,
+    ) -> Result<Vec<UploadedFile>> {
+        tracing::info!("Uploading file with fullpath: {}", full_path);
+
+        let mut form = multipart::Form::new()
+            .text("bucket", bucket.unwrap_or(&self.bucket).to_string())
+            .text("fullpath", full_path.to_string());
+
+        // Add category if provided
+        if let Some(cat) = category {
+            form = form.text("category", cat.to_string());
+        }
+
+        let part = file.into_part(filename.clone()).await?;
+        form = form.part("file", part);
+
+        // 같은 경로로의 동시 업로드는 순서대로 처리
+        let _upload_guard = self.lock_upload_key(full_path).await;
+
+        // 잠금을 잡은 뒤 R2에서 직접 확인해야 먼저 끝난 동시 업로드를 덮어쓰지 않음
+        if !overwrite && self.key_exists(bucket, full_path).await? {
+            tracing::info!("Refusing to overwrite existing file: {}", full_path);
+            return Err(KeyExists { key: full_path.to_string(), url: self.file_url(full_path) }.into());
+        }
+
+        let response = inject_trace_context(self.client.post(&self.upload_url))
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to send upload request for {}: {}", filename, e);
+                anyhow::anyhow!("Upload request failed for {}: {}", filename, e)
+            })?;
+
+        if response.status().is_success() {
+            let mut result = response.json::<FileUploadResponse>().await?;
+
+            // Map API fields for backward compatibility
+            for uploaded_file in &mut result.uploaded {
+                uploaded_file.filename = uploaded_file.original_file.clone();
+                uploaded_file.url = format!("http://localhost:5001/assets/{}", uploaded_file.file);
+            }
+
+            tracing::info!("Successfully uploaded file: {}", filename);
+            Ok(result.uploaded)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            tracing::error!("Upload failed for {}: {} - {}", filename, status, error_text);
+            anyhow::bail!("Failed to upload file {}: {} - {}", filename, status, error_text)
+        }
+    }
+
+    // 캐시를 거치지 않고 R2에 키가 있는지 확인 (덮어쓰기 방지용)
+    pub async fn key_exists(&self, bucket: Option<&str>, key: &str) -> Result<bool> {
+        let url = format!("{}/download/{}", self.base_url, key);
+        let response = inject_trace_context(self.client.head(&url))
+            .query(&[("bucket", bucket.unwrap_or(&self.bucket))])
+            .send()
+            .await?;
+        match response.status() {
+            reqwest::StatusCode::NOT_FOUND => Ok(false),
+            status if status.is_success() => Ok(true),
+            status => anyhow::bail!("Failed to check whether {} exists: {}", key, status),
+        }
+    }
+
+    pub fn file_url(&self, key: &str) -> String {
+        format!("{}/file?key={}", self.base_url, key)
+    }
+
+    // 경로별 업로드 잠금 획득 (사용 중이 아닌 잠금 항목은 정리)
+    async fn lock_upload_key(&self, key: &str) -> OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.upload_locks.lock().expect("upload lock map poisoned");
+            locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+            locks.entry(key.to_string()).or_default().clone()
+        };
+        lock.lock_owned().await
+    }
+
+    #[tracing::instrument(skip(self))]
     pub async fn delete_file(&self, bucket: Option<&str>, key: &str) -> Result<DeleteFileResponse> {
         let url = format!("{}/delete-file", self.base_url);
 
@@ -135,8 +556,7 @@ impl FileService {
             key: key.to_string(),
         };
 
-        let response = self.client
-            .post(&url)
+        let response = inject_trace_context(self.client.post(&url))
             .json(&request)
             .send()
             .await?;
@@ -149,15 +569,16 @@ impl FileService {
         }
     }
     
-    pub async fn unlink_file(&self, key: &str) -> Result<()> {
-        let url = "https://r2-api.reengki.com/unlink";
+    #[tracing::instrument(skip(self))]
+    pub async fn unlink_file(&self, bucket: Option<&str>, key: &str) -> Result<()> {
+        let url = format!("{}/unlink", self.base_url);
         
         let request = serde_json::json!({
+            "bucket": bucket.unwrap_or(&self.bucket),
             "key": key
         });
 
-        let response = self.client
-            .delete(url)
+        let response = inject_trace_context(self.client.delete(&url))
             .json(&request)
             .send()
             .await?;
@@ -187,12 +608,24 @@ impl FileService {
         }
     }
 
+    /// Unlinks every key, logging (not returning) individual failures; returns the keys removed
+    pub async fn unlink_files(&self, bucket: Option<&str>, keys: &[String]) -> Vec<String> {
+        let mut removed = Vec::new();
+        for key in keys {
+            match self.unlink_file(bucket, key).await {
+                Ok(()) => removed.push(key.clone()),
+                Err(e) => tracing::error!("Failed to unlink {}: {}", key, e),
+            }
+        }
+        removed
+    }
+
+    #[tracing::instrument(skip(self))]
     pub async fn get_folder_files(&self, bucket: Option<&str>, key: &str) -> Result<R2FolderFilesResponse> {
         let url = format!("{}/folder-files", self.base_url);
         let bucket_name = bucket.unwrap_or(&self.bucket);
         
-        let response = self.client
-            .get(&url)
+        let response = inject_trace_context(self.client.get(&url))
             .query(&[("bucket", bucket_name), ("key", key)])
             .send()
             .await?;
@@ -205,6 +638,63 @@ impl FileService {
         }
     }
 
+    // 카테고리별 폴더 내 파일 키 목록 조회 (assets folder-files)
+    pub async fn list_folder_keys_with_category(&self, key: &str, category: &str) -> Result<Option<Vec<String>>> {
+        let url = self.folder_files_url.clone();
+        self.fetch_folder_keys(&url, &[("key", key), ("category", category)]).await
+    }
+
+    // 응답이 성공이 아니면 None, 배열 또는 {items: [...]} 형태 모두 지원
+    #[tracing::instrument(skip(self))]
+    async fn fetch_folder_keys(&self, url: &str, query: &[(&str, &str)]) -> Result<Option<Vec<String>>> {
+        let response = inject_trace_context(self.client.get(url))
+            .query(query)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            tracing::info!("Folder listing not available: {} (status: {})", url, response.status());
+            return Ok(None);
+        }
+
+        let content: serde_json::Value = response.json().await?;
+        let items = content.as_array()
+            .or_else(|| content.get("items").and_then(|items| items.as_array()))
+            .ok_or_else(|| anyhow::anyhow!("Unexpected folder listing format"))?;
+
+        Ok(Some(
+            items.iter()
+                .filter_map(|item| item.get("key").and_then(|key| key.as_str()))
+                .map(str::to_string)
+                .collect(),
+        ))
+    }
+
+    pub async fn fetch_subtitle(&self, path: &str) -> Result<reqwest::Response> {
+        self.download(path).await
+    }
+
+    pub async fn fetch_image(&self, path: &str) -> Result<reqwest::Response> {
+        self.download(path).await
+    }
+
+    // 파일 내용 전체 (없으면 None, 그 외 오류 상태는 Err)
+    pub async fn fetch_file_bytes(&self, path: &str) -> Result<Option<Bytes>> {
+        let response = self.download(path).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = response.error_for_status()?;
+        Ok(Some(response.bytes().await?))
+    }
+
+    // R2 API download (상태 코드 처리는 호출 측에서)
+    #[tracing::instrument(skip(self))]
+    async fn download(&self, path: &str) -> Result<reqwest::Response> {
+        let url = format!("{}/download/{}", self.base_url, path);
+        Ok(inject_trace_context(self.client.get(&url)).send().await?)
+    }
+
     pub async fn get_all_files(&self, _bucket: Option<&str>) -> Result<R2AllFilesResponse> {
         // 메모리 캐시에서 전체 데이터 가져오기
         let all_files_data = self.get_cached_all_files().await?;
@@ -230,7 +720,7 @@ impl FileService {
     }
 
     pub async fn get_r2_folder_files(&self, key: &str) -> Result<R2WorkerFolderResponse> {
-        self.get_r2_folder_files_with_category(key, "reengkigo").await
+        self.get_r2_folder_files_with_category(key, &self.default_category).await
     }
     
     pub async fn get_r2_folder_files_with_category(&self, key: &str, category: &str) -> Result<R2WorkerFolderResponse> {
@@ -255,21 +745,106 @@ impl FileService {
         Ok(filtered_files)
     }
     
-    // 전체 데이터 로드를 위한 직접 API 호출 (최적화된 병렬 페이지네이션)
-    async fn get_r2_folder_files_direct(&self, key: &str) -> Result<R2WorkerFolderResponse> {
-        self.get_r2_folder_files_direct_with_category(key, "reengkigo").await
-    }
-    
+    // 전체 데이터 로드를 위한 직접 API 호출
+    #[tracing::instrument(skip(self))]
     async fn get_r2_folder_files_direct_with_category(&self, key: &str, category: &str) -> Result<R2WorkerFolderResponse> {
-        let base_url = "https://assets.reengkigo.com/folder-files";
+        match self.parallel_listing {
+            Some(listing) => self.get_r2_folder_files_parallel(key, category, listing).await,
+            None => self.get_r2_folder_files_by_cursor(key, category).await,
+        }
+    }
+
+    // nextCursor를 따라 모든 페이지를 순차 수집
+    async fn get_r2_folder_files_by_cursor(&self, key: &str, category: &str) -> Result<R2WorkerFolderResponse> {
         let start_time = Instant::now();
         
         tracing::info!("Fetching R2 folder files with key: {} and category: {}", key, category);
-        
-        // Try simple API call first (no pagination parameters)
-        let response = self.client
-            .get(base_url)
+
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        let mut pages = 0;
+
+        loop {
+            let cursor_param: Vec<(&str, String)> = cursor.iter().map(|c| ("cursor", c.clone())).collect();
+            let page = self.fetch_r2_folder_page(key, category, &cursor_param).await?;
+            pages += 1;
+            items.extend(page.items);
+
+            let next_cursor = match page.next_cursor {
+                Some(next) if page.list_complete != Some(true) && !next.is_empty() => next,
+                _ => break,
+            };
+            if cursor.as_deref() == Some(next_cursor.as_str()) {
+                tracing::warn!("R2 listing returned the same cursor twice, stopping at {} items", items.len());
+                break;
+            }
+            if pages >= MAX_LISTING_PAGES {
+                anyhow::bail!("R2 listing exceeded {} pages", MAX_LISTING_PAGES);
+            }
+            cursor = Some(next_cursor);
+        }
+
+        tracing::info!("Fetched {} items in {} page(s) in {:?}", items.len(), pages, start_time.elapsed());
+        Ok(items)
+    }
+
+    // 최적화된 병렬 페이지네이션: concurrency개의 offset 페이지를 동시에 요청하고,
+    // page_size보다 짧은 페이지나 새 키가 없는 페이지(offset을 무시하는 Worker)가 나오면 종료
+    async fn get_r2_folder_files_parallel(&self, key: &str, category: &str, listing: ParallelListing) -> Result<R2WorkerFolderResponse> {
+        let start_time = Instant::now();
+        let limit = listing.page_size.to_string();
+        let mut items = Vec::new();
+        let mut seen_keys = HashSet::new();
+        let mut offset = 0;
+        let mut pages = 0;
+
+        tracing::info!("Fetching R2 folder files in parallel (page size {}, concurrency {}) with key: {} and category: {}",
+            listing.page_size, listing.concurrency, key, category);
+
+        loop {
+            let offsets = (0..listing.concurrency).map(|i| offset + i * listing.page_size);
+            let mut batch: Vec<(usize, R2WorkerPaginatedResponse)> = stream::iter(offsets)
+                .map(|page_offset| {
+                    let params = [("offset", page_offset.to_string()), ("limit", limit.clone())];
+                    async move {
+                        let page = self.fetch_r2_folder_page(key, category, &params).await?;
+                        Ok::<_, anyhow::Error>((page_offset, page))
+                    }
+                })
+                .buffer_unordered(listing.concurrency)
+                .try_collect()
+                .await?;
+            batch.sort_by_key(|(page_offset, _)| *page_offset);
+
+            for (page_offset, page) in batch {
+                pages += 1;
+                let is_last = page.items.len() < listing.page_size || page.list_complete == Some(true);
+                let before = items.len();
+                items.extend(page.items.into_iter().filter(|item| seen_keys.insert(item.key.clone())));
+                let repeated = items.len() == before && !is_last;
+                if repeated {
+                    tracing::warn!("R2 listing page at offset {} had no new keys (offset/limit ignored?), stopping at {} items", page_offset, items.len());
+                }
+                if is_last || repeated {
+                    tracing::info!("Fetched {} items in {} page(s) in {:?}", items.len(), pages, start_time.elapsed());
+                    return Ok(items);
+                }
+                if pages >= MAX_LISTING_PAGES {
+                    anyhow::bail!("R2 listing exceeded {} pages", MAX_LISTING_PAGES);
+                }
+            }
+
+            offset += listing.concurrency * listing.page_size;
+        }
+    }
+
+    // folder-files 한 페이지 조회 (배열 응답은 커서 없는 페이지로 취급)
+    async fn fetch_r2_folder_page(&self, key: &str, category: &str, page_params: &[(&str, String)]) -> Result<R2WorkerPaginatedResponse> {
+        let response = inject_trace_context(self.client.get(&self.folder_files_url))
             .query(&[("key", key), ("category", category)])
+            .query(page_params)
+            // 업로드용 긴 클라이언트 타임아웃 대신 목록 조회 전용 타임아웃
+            .timeout(Duration::from_secs(self.http_client.listing_timeout_seconds))
             .send()
             .await?;
             
@@ -278,32 +853,44 @@ impl FileService {
             anyhow::bail!("Failed to get R2 folder files: {}", response.status())
         }
         
-        // Try to parse as direct array first
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("")
+            .to_string();
         let response_text = response.text().await?;
         tracing::debug!("API response length: {} chars", response_text.len());
         
         // Try parsing as direct array (Vec<R2WorkerFileItem>)
         if let Ok(items) = serde_json::from_str::<R2WorkerFolderResponse>(&response_text) {
-            tracing::info!("Direct array parse successful: {} items in {:?}", 
-                items.len(), start_time.elapsed());
-            return Ok(items);
+            return Ok(R2WorkerPaginatedResponse {
+                count: items.len() as u64,
+                items,
+                next_cursor: None,
+                list_complete: None,
+            });
         }
         
         // If that fails, try parsing as paginated response
         if let Ok(paginated) = serde_json::from_str::<R2WorkerPaginatedResponse>(&response_text) {
-            tracing::info!("Paginated response parse successful: {} items in {:?}", 
-                paginated.items.len(), start_time.elapsed());
-            return Ok(paginated.items);
+            return Ok(paginated);
         }
         
-        tracing::error!("Failed to parse API response as either direct array or paginated response");
-        tracing::debug!("Response text preview: {}", &response_text[..response_text.len().min(500)]);
-        anyhow::bail!("Failed to parse API response")
+        let preview = body_preview(&response_text);
+        // 200이지만 HTML 오류 페이지 등 (예: Cloudflare 확인 페이지)
+        if !content_type.to_ascii_lowercase().contains("json") {
+            tracing::error!("R2 folder-files returned non-JSON content-type '{}': {}", content_type, preview);
+            return Err(NonJsonResponse { content_type, preview }.into());
+        }
+
+        tracing::error!("Failed to parse API response as either direct array or paginated response: {}", preview);
+        anyhow::bail!("Failed to parse API response: {}", preview)
     }
     
     // 메모리 캐시에서 전체 데이터 가져오기 (캐시가 없으면 로드)
     async fn get_cached_all_files(&self) -> Result<R2WorkerFolderResponse> {
-        self.get_cached_all_files_with_category("reengkigo").await
+        self.get_cached_all_files_with_category(&self.default_category).await
     }
     
     async fn get_cached_all_files_with_category(&self, category: &str) -> Result<R2WorkerFolderResponse> {
@@ -313,7 +900,13 @@ impl FileService {
         if let Some(cache) = cache_read.get(category) {
             if !cache.is_expired() {
                 tracing::info!("Cache hit for all_files category '{}' (age: {:?})", category, cache.created_at.elapsed());
-                return Ok(cache.data.clone());
+                cache.touch();
+                return cache.data.to_vec(&self.cache_metrics);
+            }
+            if cache.is_serving_stale() {
+                cache.touch();
+                tracing::warn!("Serving stale all_files for category '{}' (age: {:?})", category, cache.created_at.elapsed());
+                return cache.data.to_vec(&self.cache_metrics);
             }
         }
         
@@ -323,14 +916,11 @@ impl FileService {
     }
     
     // 전체 데이터가 캐시에 로드되어 있는지 확인하고 없으면 로드
-    async fn ensure_all_files_loaded(&self) -> Result<()> {
-        self.ensure_all_files_loaded_with_category("reengkigo").await
-    }
-    
     async fn ensure_all_files_loaded_with_category(&self, category: &str) -> Result<()> {
         let cache_read = self.all_files_cache.read().await;
         if let Some(cache) = cache_read.get(category) {
             if !cache.is_expired() {
+                self.cache_metrics.hits.fetch_add(1, Ordering::Relaxed);
                 // 캐시가 곧 만료될 예정이면 백그라운드에서 미리 갱신
                 if cache.created_at.elapsed() > Duration::from_secs(480) { // 8분 경과
                     drop(cache_read);
@@ -338,37 +928,31 @@ impl FileService {
                 }
                 return Ok(());
             }
+            if cache.is_serving_stale() {
+                self.cache_metrics.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(());
+            }
         }
         drop(cache_read);
+        self.cache_metrics.misses.fetch_add(1, Ordering::Relaxed);
         
         self.load_all_files_to_cache_with_category(category).await?;
         Ok(())
     }
     
     // 백그라운드에서 캐시 갱신 (현재 캐시는 유지하면서 새 데이터 로드)
-    fn refresh_cache_in_background(&self) {
-        self.refresh_cache_in_background_with_category("reengkigo");
-    }
-    
     fn refresh_cache_in_background_with_category(&self, category: &str) {
-        let cache = self.all_files_cache.clone();
         let service = self.clone();
         let category = category.to_string();
+        self.cache_metrics.background_refreshes.fetch_add(1, Ordering::Relaxed);
         
         tokio::spawn(async move {
             tracing::info!("Starting background cache refresh for category: {}", category);
             match service.get_r2_folder_files_direct_with_category("*", &category).await {
                 Ok(new_data) => {
-                    let ttl = Duration::from_secs(1800); // 30분 TTL (더 길게)
-                    let cache_entry = AllFilesCache {
-                        data: new_data.clone(),
-                        created_at: Instant::now(),
-                        ttl,
-                    };
-                    
-                    let mut cache_write = cache.write().await;
-                    cache_write.insert(category.clone(), cache_entry);
-                    tracing::info!("Background cache refresh completed with {} files for category: {}", new_data.len(), category);
+                    let count = new_data.len();
+                    service.store_all_files(&category, new_data).await;
+                    tracing::info!("Background cache refresh completed with {} files for category: {}", count, category);
                 }
                 Err(e) => {
                     tracing::error!("Failed to refresh cache in background for category {}: {}", category, e);
@@ -378,10 +962,6 @@ impl FileService {
     }
     
     // 전체 데이터를 캐시에 로드
-    async fn load_all_files_to_cache(&self) -> Result<R2WorkerFolderResponse> {
-        self.load_all_files_to_cache_with_category("reengkigo").await
-    }
-    
     async fn load_all_files_to_cache_with_category(&self, category: &str) -> Result<R2WorkerFolderResponse> {
         tracing::info!("Loading all files to cache from R2 Worker API for category: {}", category);
         
@@ -390,36 +970,83 @@ impl FileService {
             let cache_read = self.all_files_cache.read().await;
             if let Some(cache) = cache_read.get(category) {
                 // 다른 스레드가 방금 로드했을 수 있음
-                if cache.created_at.elapsed() < Duration::from_secs(5) {
+                if !cache.is_expired() {
                     tracing::info!("Cache was just loaded by another thread for category: {}", category);
-                    return Ok(cache.data.clone());
+                    return cache.data.to_vec(&self.cache_metrics);
                 }
             }
         }
         
         // R2 Worker API에서 전체 데이터 가져오기 ("*" 사용)
-        let worker_response = self.get_r2_folder_files_direct_with_category("*", category).await?;
-        
-        // 캐시에 저장
-        let ttl = Duration::from_secs(1800); // 30분 TTL로 증가
-        let cache_entry = AllFilesCache {
-            data: worker_response.clone(),
-            created_at: Instant::now(),
-            ttl,
+        let worker_response = match self.get_r2_folder_files_direct_with_category("*", category).await {
+            Ok(worker_response) => worker_response,
+            Err(e) => return self.stale_after_failed_refresh(category, e).await,
         };
         
-        {
-            let mut cache_write = self.all_files_cache.write().await;
-            cache_write.insert(category.to_string(), cache_entry);
-        }
+        // 캐시에 저장
+        self.store_all_files(category, worker_response.clone()).await;
         
-        tracing::info!("Cached all_files with {} files for category: {} (TTL: {:?})", worker_response.len(), category, ttl);
+        tracing::info!("Cached all_files with {} files for category: {} (TTL: {:?})", worker_response.len(), category, self.cache_ttl);
         Ok(worker_response)
     }
+
+    // 카테고리 목록을 캐시에 저장하고, 항목 수 상한을 넘으면 LRU 카테고리 제거
+    async fn store_all_files(&self, category: &str, data: R2WorkerFolderResponse) {
+        let mut cache_write = self.all_files_cache.write().await;
+        let data = CachedListing::new(data, self.compress_cache);
+        cache_write.insert(category.to_string(), AllFilesCache::new(data, self.cache_ttl));
+
+        let Some(max_items) = self.cache_max_items else { return };
+        loop {
+            let total: usize = cache_write.values().map(|cache| cache.data.len()).sum();
+            if total <= max_items {
+                break;
+            }
+            // 방금 저장한 카테고리는 제거하지 않음 (혼자 상한을 넘으면 그대로 둠)
+            let Some(oldest) = cache_write
+                .iter()
+                .filter(|(cached_category, _)| cached_category.as_str() != category)
+                .min_by_key(|(_, cache)| cache.last_accessed())
+                .map(|(cached_category, _)| cached_category.clone())
+            else {
+                tracing::warn!("Category '{}' alone exceeds the cache cap of {} items ({})", category, max_items, total);
+                break;
+            };
+            cache_write.remove(&oldest);
+            tracing::info!("Evicted cached category '{}' to stay under {} items", oldest, max_items);
+        }
+    }
     
+    // 캐시된 전체 목록에서 키가 조건에 맞는 파일 조회 (R2에서 받지 않고 크기/수정일 확인용)
+    pub async fn find_cached_files(&self, category: &str, matches: impl Fn(&str) -> bool) -> Result<Vec<R2WorkerFileItem>> {
+        let all_files = self.get_cached_all_files_with_category(category).await?;
+        Ok(all_files.into_iter().filter(|item| matches(&item.key)).collect())
+    }
+
+    // 갱신 실패 시 만료된 캐시가 있으면 그 데이터를 계속 제공 (stale-while-error)
+    async fn stale_after_failed_refresh(&self, category: &str, error: anyhow::Error) -> Result<R2WorkerFolderResponse> {
+        let mut cache_write = self.all_files_cache.write().await;
+        let Some(cache) = cache_write.get_mut(category) else {
+            return Err(error);
+        };
+        tracing::warn!(
+            "Refreshing all_files for category '{}' failed, serving stale data (age: {:?}): {}",
+            category, cache.created_at.elapsed(), error
+        );
+        cache.refresh_failed_at = Some(Instant::now());
+        cache.data.to_vec(&self.cache_metrics)
+    }
+
+    /// Whether listings for `category` currently come from an expired cache because R2 could not be reached
+    pub async fn is_serving_stale(&self, category: &str) -> bool {
+        self.all_files_cache.read().await
+            .get(category)
+            .is_some_and(|cache| cache.is_expired() && cache.refresh_failed_at.is_some())
+    }
+
     // 폴더 구조를 위한 경로 기반 폴더 조회 (메모리 필터링)
     pub async fn get_folder_structure(&self, prefix: &str) -> Result<Vec<String>> {
-        self.get_folder_structure_with_category(prefix, "reengkigo").await
+        self.get_folder_structure_with_category(prefix, &self.default_category).await
     }
     
     pub async fn get_folder_structure_with_category(&self, prefix: &str, category: &str) -> Result<Vec<String>> {
@@ -463,7 +1090,96 @@ impl FileService {
         Ok(result)
     }
 
+    // 폴더별 최신 수정일 조회 (메모리 필터링)
+    pub async fn get_folder_modified_dates_with_category(&self, prefix: &str, category: &str) -> Result<HashMap<String, String>> {
+        let all_files = self.get_cached_all_files_with_category(category).await?;
+        Ok(latest_modified_by_folder(&all_files, prefix))
+    }
+
+    // R2에 존재하는 카테고리 목록 (캐시된 항목 기준, 정렬됨)
+    pub async fn list_categories(&self) -> Result<Vec<String>> {
+        // 기본 카테고리는 최소한 로드되어 있어야 함
+        self.ensure_all_files_loaded_with_category(&self.default_category).await?;
+
+        let cache_read = self.all_files_cache.read().await;
+        let mut categories = std::collections::BTreeSet::new();
+        for (cached_category, cache) in cache_read.iter() {
+            categories.insert(cached_category.clone());
+            // category가 없는 항목은 조회한 카테고리에 속한 것으로 간주
+            categories.extend(cache.data.to_vec(&self.cache_metrics)?.into_iter().filter_map(|item| item.value.category));
+        }
+
+        Ok(categories.into_iter().filter(|category| !category.is_empty()).collect())
+    }
+
+    // 사이드바용 중첩 폴더 트리 (메모리 필터링)
+    pub async fn get_folder_tree_with_category(&self, category: &str, depth: usize) -> Result<Vec<FolderTreeNode>> {
+        let all_files = self.get_cached_all_files_with_category(category).await?;
+        Ok(build_folder_tree(&all_files, depth))
+    }
+
+    // 최근 수정된 파일 목록 (category가 없으면 모든 카테고리, 메모리 기준)
+    pub async fn recent_files(&self, category: Option<&str>, limit: usize) -> Result<Vec<R2WorkerFileItem>> {
+        let categories = match category {
+            Some(category) => vec![category.to_string()],
+            None => self.list_categories().await?,
+        };
+
+        let mut seen = std::collections::HashSet::new();
+        let mut files = Vec::new();
+        for listed_category in &categories {
+            let all_files = match self.get_cached_all_files_with_category(listed_category).await {
+                Ok(all_files) => all_files,
+                Err(e) if category.is_none() => {
+                    tracing::warn!("Skipping category '{}' in recent files: {}", listed_category, e);
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            for mut item in all_files {
+                if item.key.ends_with('/') {
+                    continue;
+                }
+                // 다른 카테고리 목록에도 나오는 항목은 한 번만
+                let item_category = item.value.category.get_or_insert_with(|| listed_category.clone()).clone();
+                if seen.insert((item_category, item.key.clone())) {
+                    files.push(item);
+                }
+            }
+        }
+
+        sort_by_recently_modified(&mut files);
+        files.truncate(limit);
+        Ok(files)
+    }
+
     // 캐시 관리 메서드들
+
+    // 캐시를 건너뛰고 R2에서 다시 받아 캐시 갱신.
+    // 최근 FORCED_REFRESH_MIN_INTERVAL 내에 갱신했다면 false를 반환하고 기존 캐시 유지
+    pub async fn force_refresh_with_category(&self, category: &str) -> Result<bool> {
+        {
+            let mut last_refresh = self.last_forced_refresh.write().await;
+            if let Some(last) = last_refresh.get(category) {
+                if last.elapsed() < FORCED_REFRESH_MIN_INTERVAL {
+                    tracing::info!("Forced refresh for category '{}' rate-limited (last {:?} ago)", category, last.elapsed());
+                    return Ok(false);
+                }
+            }
+            last_refresh.insert(category.to_string(), Instant::now());
+        }
+
+        let data = self.get_r2_folder_files_direct_with_category("*", category).await?;
+        self.store_all_files(category, data).await;
+
+        tracing::info!("Forced cache refresh completed for category: {}", category);
+        Ok(true)
+    }
+
+    // 시작 시 카테고리 전체 데이터를 미리 로드
+    pub async fn warm_cache(&self, category: &str) -> Result<()> {
+        self.ensure_all_files_loaded_with_category(category).await
+    }
     
     // 전체 캐시 초기화 (첫 진입 시)
     pub async fn clear_all_cache(&self) {
@@ -473,11 +1189,22 @@ impl FileService {
     }
     
     // 업로드/삭제 시 캐시 무효화 
-    pub async fn invalidate_cache_for_path(&self, _path: &str) {
+    pub async fn invalidate_cache_for_path(&self, path: &str) {
         // 모든 카테고리 캐시 무효화
         let mut cache_write = self.all_files_cache.write().await;
         cache_write.clear();
-        tracing::info!("All category caches invalidated due to path change: {}", _path);
+        drop(cache_write);
+        tracing::info!("All category caches invalidated due to path change: {}", path);
+
+        // 구독자가 없으면 send가 실패하지만 무시해도 됨
+        let _ = self.folder_events.send(FolderChangeEvent {
+            path: path.to_string(),
+            timestamp: chrono::Utc::now(),
+        });
+    }
+
+    pub fn subscribe_folder_events(&self) -> broadcast::Receiver<FolderChangeEvent> {
+        self.folder_events.subscribe()
     }
     
     // 만료된 캐시 정리 (자동으로 처리됨)
@@ -495,6 +1222,15 @@ impl FileService {
         }
     }
     
+    pub fn cache_counters(&self) -> CacheCounters {
+        CacheCounters {
+            hits: self.cache_metrics.hits.load(Ordering::Relaxed),
+            misses: self.cache_metrics.misses.load(Ordering::Relaxed),
+            background_refreshes: self.cache_metrics.background_refreshes.load(Ordering::Relaxed),
+            decompressions: self.cache_metrics.decompressions.load(Ordering::Relaxed),
+        }
+    }
+
     // 캐시 통계 정보 반환
     pub async fn get_cache_stats(&self) -> (usize, usize) {
         let cache_read = self.all_files_cache.read().await;
@@ -508,6 +1244,85 @@ impl FileService {
 
 }
 
+/// Returns the newest `modified_date` of the files under each direct child folder of `prefix`.
+///
+/// An empty `prefix` means the root level. Dates are compared as ISO-8601 strings.
+pub fn latest_modified_by_folder(files: &[R2WorkerFileItem], prefix: &str) -> HashMap<String, String> {
+    let folder_prefix = if prefix.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", prefix.trim_end_matches('/'))
+    };
+
+    let mut latest: HashMap<String, String> = HashMap::new();
+    for item in files {
+        let Some(modified) = item.value.modified_date.as_ref() else { continue };
+        let Some(remaining) = item.key.strip_prefix(&folder_prefix) else { continue };
+        let Some((folder_name, _)) = remaining.split_once('/') else { continue };
+        if folder_name.is_empty() {
+            continue;
+        }
+
+        match latest.get_mut(folder_name) {
+            Some(current) if *current >= *modified => {}
+            Some(current) => *current = modified.clone(),
+            None => {
+                latest.insert(folder_name.to_string(), modified.clone());
+            }
+        }
+    }
+
+    latest
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FolderTreeNode {
+    pub name: String,
+    pub path: String,
+    pub children: Vec<FolderTreeNode>,
+}
+
+/// Builds the nested folder tree implied by the file keys, at most `depth` levels deep.
+///
+/// Only folders are included; children are sorted by name.
+pub fn build_folder_tree(files: &[R2WorkerFileItem], depth: usize) -> Vec<FolderTreeNode> {
+    #[derive(Default)]
+    struct Level(BTreeMap<String, Level>);
+
+    let mut root = Level::default();
+    for item in files {
+        // 마지막 세그먼트는 파일 이름 (폴더 표시용 키 "a/b/"는 빈 세그먼트)
+        let mut segments: Vec<&str> = item.key.split('/').collect();
+        segments.pop();
+        let mut level = &mut root;
+        for segment in segments.into_iter().filter(|segment| !segment.is_empty()).take(depth) {
+            level = level.0.entry(segment.to_string()).or_default();
+        }
+    }
+
+    fn into_nodes(level: Level, parent: &str) -> Vec<FolderTreeNode> {
+        level.0.into_iter()
+            .map(|(name, children)| {
+                let path = if parent.is_empty() { name.clone() } else { format!("{}/{}", parent, name) };
+                FolderTreeNode { children: into_nodes(children, &path), name, path }
+            })
+            .collect()
+    }
+    into_nodes(root, "")
+}
+
+/// Sorts files by `modified_date`, newest first; files without a date go last.
+///
+/// Dates are compared as ISO-8601 strings, as in [`latest_modified_by_folder`].
+pub fn sort_by_recently_modified(files: &mut [R2WorkerFileItem]) {
+    files.sort_by(|a, b| match (&a.value.modified_date, &b.value.modified_date) {
+        (Some(a_date), Some(b_date)) => b_date.cmp(a_date),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.key.cmp(&b.key),
+    });
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct R2FileInfo {
     pub key: String,
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// 같은 키로 재시도하면 이 기간 동안 처음 결과를 그대로 돌려줌
+pub const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+enum Entry {
+    // 처음 요청이 아직 처리 중
+    InFlight,
+    Completed { stored_at: Instant, body: serde_json::Value },
+}
+
+type Entries = Arc<Mutex<HashMap<String, Entry>>>;
+
+/// What a request carrying an `Idempotency-Key` should do
+pub enum Reservation {
+    /// First request for the key; store the result with [`IdempotencyGuard::complete`]
+    Reserved(IdempotencyGuard),
+    /// Another request with the same key is still running
+    InFlight,
+    /// The key already completed; replay this body
+    Completed(serde_json::Value),
+}
+
+/// Holds the in-flight reservation for a key.
+///
+/// Dropping it without [`Self::complete`] (a failed or aborted request)
+/// releases the key so the client can retry with it.
+pub struct IdempotencyGuard {
+    key: Option<String>,
+    entries: Entries,
+}
+
+impl IdempotencyGuard {
+    pub fn complete(mut self, body: serde_json::Value) {
+        if let Some(key) = self.key.take() {
+            let mut entries = self.entries.lock().unwrap();
+            entries.insert(key, Entry::Completed { stored_at: Instant::now(), body });
+        }
+    }
+}
+
+impl Drop for IdempotencyGuard {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.entries.lock().unwrap().remove(&key);
+        }
+    }
+}
+
+/// Results of completed requests keyed by their `Idempotency-Key`
+#[derive(Clone)]
+pub struct IdempotencyStore {
+    ttl: Duration,
+    entries: Entries,
+}
+
+impl Default for IdempotencyStore {
+    fn default() -> Self {
+        Self::new(IDEMPOTENCY_KEY_TTL)
+    }
+}
+
+impl IdempotencyStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Claims `key` before the request does any work, so a concurrent retry
+    /// sees [`Reservation::InFlight`] instead of running the request twice
+    pub fn reserve(&self, key: &str) -> Reservation {
+        let mut entries = self.entries.lock().unwrap();
+        // 예약할 때 만료된 결과 정리
+        entries.retain(|_, entry| match entry {
+            Entry::InFlight => true,
+            Entry::Completed { stored_at, .. } => stored_at.elapsed() < self.ttl,
+        });
+        match entries.get(key) {
+            Some(Entry::InFlight) => Reservation::InFlight,
+            Some(Entry::Completed { body, .. }) => Reservation::Completed(body.clone()),
+            None => {
+                entries.insert(key.to_string(), Entry::InFlight);
+                Reservation::Reserved(IdempotencyGuard {
+                    key: Some(key.to_string()),
+                    entries: self.entries.clone(),
+                })
+            }
+        }
+    }
+}
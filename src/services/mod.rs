@@ -1,3 +1,12 @@
 pub mod auth;
 pub mod file;
+pub mod idempotency;
 pub mod dashboard;
+pub mod audit;
+pub mod user;
+pub mod asset;
+pub mod project_list;
+pub mod revocation;
+pub mod subtitle;
+pub mod upload_session;
+pub mod youtube;
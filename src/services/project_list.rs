@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use figment::{
+    providers::{Format, Yaml},
+    Figment,
+};
+use thiserror::Error;
+
+pub const PROJECT_LIST_PATH: &str = "project_list.yaml";
+
+/// Stage name -> (month key -> book_id), e.g. `Stage1-1.month_05 = R1B`
+pub type ProjectList = BTreeMap<String, BTreeMap<String, String>>;
+
+#[derive(Debug, Error)]
+pub enum ProjectListError {
+    #[error("project_list.yaml not found at '{path}' (working directory: {cwd})")]
+    NotFound { path: PathBuf, cwd: String },
+    #[error("failed to read project_list.yaml: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to parse project_list.yaml: {0}")]
+    Parse(String),
+}
+
+pub fn read_project_list(path: &Path) -> Result<String, ProjectListError> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let cwd = std::env::current_dir()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_else(|_| "<unknown>".to_string());
+            tracing::error!("project_list.yaml not found at '{}' (working directory: {})", path.display(), cwd);
+            Err(ProjectListError::NotFound { path: path.to_path_buf(), cwd })
+        }
+        Err(e) => Err(ProjectListError::Io(e)),
+    }
+}
+
+pub fn load_project_list(path: &Path) -> Result<ProjectList, ProjectListError> {
+    let content = read_project_list(path)?;
+    Figment::from(Yaml::string(&content))
+        .extract()
+        .map_err(|e| ProjectListError::Parse(e.to_string()))
+}
+
+// stage/month로 book_id 조회 (매핑이 없으면 None)
+pub fn get_book_id_from_mapping(path: &Path, stage: &str, month: &str) -> Result<Option<String>, ProjectListError> {
+    let project_list = load_project_list(path)?;
+    Ok(project_list.get(stage).and_then(|months| months.get(month)).cloned())
+}
+
+// book_id로 (stage, month) 역조회
+pub fn find_stage_and_month(project_list: &ProjectList, book_id: &str) -> Option<(String, String)> {
+    project_list.iter().find_map(|(stage, months)| {
+        months
+            .iter()
+            .find(|(_, id)| id.as_str() == book_id)
+            .map(|(month, _)| (stage.clone(), month.clone()))
+    })
+}
+
+/// Parsed project_list.yaml, loaded on first use and re-read only by `reload`
+pub struct ProjectListCache {
+    path: PathBuf,
+    cached: RwLock<Option<Arc<ProjectList>>>,
+}
+
+impl ProjectListCache {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            cached: RwLock::new(None),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self) -> Result<Arc<ProjectList>, ProjectListError> {
+        if let Some(project_list) = self.cached.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            return Ok(project_list.clone());
+        }
+        self.reload()
+    }
+
+    // 파일을 다시 읽어 캐시 교체 (실패하면 기존 캐시 유지)
+    pub fn reload(&self) -> Result<Arc<ProjectList>, ProjectListError> {
+        let project_list = Arc::new(load_project_list(&self.path)?);
+        *self.cached.write().unwrap_or_else(|e| e.into_inner()) = Some(project_list.clone());
+        tracing::info!("Loaded project_list.yaml from '{}' ({} stages)", self.path.display(), project_list.len());
+        Ok(project_list)
+    }
+
+    pub fn get_book_id(&self, stage: &str, month: &str) -> Result<Option<String>, ProjectListError> {
+        Ok(self.get()?.get(stage).and_then(|months| months.get(month)).cloned())
+    }
+}
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use chrono::Utc;
+
+/// Revoked token ids (`jti`), kept until the token would have expired anyway.
+/// Owned by the `AuthService` in `AppState`.
+#[derive(Default)]
+pub struct RevocationList {
+    revoked: RwLock<HashMap<String, i64>>, // jti -> exp (unix seconds)
+}
+
+impl RevocationList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 추가할 때 이미 만료된 항목 정리
+    pub fn revoke(&self, jti: &str, expires_at: i64) {
+        let now = Utc::now().timestamp();
+        let mut revoked = self.revoked.write().unwrap_or_else(|e| e.into_inner());
+        revoked.retain(|_, exp| *exp > now);
+        revoked.insert(jti.to_string(), expires_at);
+    }
+
+    pub fn is_revoked(&self, jti: &str) -> bool {
+        self.revoked.read().unwrap_or_else(|e| e.into_inner()).contains_key(jti)
+    }
+}
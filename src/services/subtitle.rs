@@ -0,0 +1,67 @@
+use thiserror::Error;
+
+use crate::dto::asset::SubtitleData;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SubtitleImportError {
+    #[error("line {line}: {message}")]
+    InvalidRow { line: u64, message: String },
+    #[error("invalid CSV: {0}")]
+    Csv(String),
+    #[error("invalid JSON: {0}")]
+    Json(String),
+    #[error("unsupported content type '{0}' (expected text/csv or application/json)")]
+    UnsupportedContentType(String),
+}
+
+/// Parses a subtitle import body, picking the format from `content_type`
+pub fn parse_subtitles(content_type: &str, body: &str) -> Result<Vec<SubtitleData>, SubtitleImportError> {
+    let mime = content_type.split(';').next().unwrap_or("").trim().to_lowercase();
+    match mime.as_str() {
+        "text/csv" | "application/csv" => parse_subtitles_csv(body),
+        "application/json" => parse_subtitles_json(body),
+        _ => Err(SubtitleImportError::UnsupportedContentType(content_type.to_string())),
+    }
+}
+
+/// `page_num,sentence_num,text` rows; a leading header row is skipped
+pub fn parse_subtitles_csv(body: &str) -> Result<Vec<SubtitleData>, SubtitleImportError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .trim(csv::Trim::All)
+        .from_reader(body.as_bytes());
+
+    let mut subtitles = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| SubtitleImportError::Csv(e.to_string()))?;
+        let line = record.position().map(|position| position.line()).unwrap_or(index as u64 + 1);
+
+        if index == 0 && record.get(0) == Some("page_num") {
+            continue;
+        }
+        if record.len() != 3 {
+            return Err(SubtitleImportError::InvalidRow {
+                line,
+                message: format!("expected 3 columns (page_num,sentence_num,text), found {}", record.len()),
+            });
+        }
+
+        let number = |column: usize, name: &str| {
+            record[column].parse::<i32>().map_err(|_| SubtitleImportError::InvalidRow {
+                line,
+                message: format!("{} '{}' is not a number", name, &record[column]),
+            })
+        };
+        subtitles.push(SubtitleData {
+            page_num: number(0, "page_num")?,
+            sentence_num: number(1, "sentence_num")?,
+            text: record[2].to_string(),
+        });
+    }
+
+    Ok(subtitles)
+}
+
+pub fn parse_subtitles_json(body: &str) -> Result<Vec<SubtitleData>, SubtitleImportError> {
+    serde_json::from_str(body).map_err(|e| SubtitleImportError::Json(e.to_string()))
+}
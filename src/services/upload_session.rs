@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::body::Bytes;
+use futures::{Stream, StreamExt};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::utils::spool::{SpooledFile, SPILL_FILE_PREFIX};
+
+// 마지막 PATCH 이후 이 기간 동안 이어 올리지 않으면 세션과 임시 파일 삭제
+pub const UPLOAD_SESSION_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+// 만료된 세션을 정리하는 주기
+pub const UPLOAD_SESSION_SWEEP_INTERVAL: Duration = Duration::from_secs(10 * 60);
+// 계정당 동시에 열어 둘 수 있는 세션 수 (세션마다 최대 max_body_bytes를 디스크에 잡아 둠)
+pub const DEFAULT_MAX_SESSIONS_PER_ACCOUNT: usize = 5;
+
+#[derive(Debug, Error)]
+pub enum UploadSessionError {
+    #[error("upload session not found: {0}")]
+    NotFound(String),
+    #[error("upload session {0} belongs to another account")]
+    NotOwner(String),
+    #[error("too many open upload sessions (limit {limit} per account)")]
+    TooManySessions { limit: usize },
+    #[error("Upload-Offset {actual} does not match the current offset {expected}")]
+    OffsetMismatch { expected: u64, actual: u64 },
+    #[error("chunk would exceed the declared upload length of {length} bytes")]
+    ExceedsLength { length: u64 },
+    #[error("failed to read chunk: {0}")]
+    Read(String),
+    #[error("failed to write upload file: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Where the assembled file goes once every byte has arrived
+#[derive(Debug, Clone)]
+pub struct UploadTarget {
+    /// Directory part of the key, with a trailing slash (empty for the bucket root)
+    pub base_path: String,
+    pub filename: String,
+    pub category: String,
+    pub bucket: Option<String>,
+    /// When false, the final push fails if the key appeared on R2 meanwhile
+    pub overwrite: bool,
+}
+
+impl UploadTarget {
+    pub fn key(&self) -> String {
+        format!("{}{}", self.base_path, self.filename)
+    }
+}
+
+struct UploadSession {
+    target: UploadTarget,
+    length: u64,
+    offset: u64,
+    // 받은 바이트를 이어 쓰는 임시 파일, 세션이 사라지면 삭제됨 (R2 업로드가 끝나면 None)
+    temp: Option<Arc<NamedTempFile>>,
+    last_activity: Instant,
+}
+
+impl UploadSession {
+    fn is_open(&self, ttl: Duration) -> bool {
+        self.temp.is_some() && self.last_activity.elapsed() < ttl
+    }
+}
+
+// 세션을 만든 계정은 잠금 밖에 두어, 사용 중인 세션을 기다리지 않고 계정별 개수를 셀 수 있음
+#[derive(Clone)]
+struct SessionEntry {
+    // 이 계정만 이어 올리거나 취소할 수 있음
+    owner: String,
+    session: Arc<Mutex<UploadSession>>,
+}
+
+impl SessionEntry {
+    // 사용 중(잠김)인 세션은 열려 있는 것으로 봄
+    fn is_open(&self, ttl: Duration) -> bool {
+        match self.session.try_lock() {
+            Ok(session) => session.is_open(ttl),
+            Err(_) => true,
+        }
+    }
+}
+
+type Sessions = Arc<RwLock<HashMap<String, SessionEntry>>>;
+
+/// Offset/length of an open session, as reported to the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadProgress {
+    pub offset: u64,
+    pub length: u64,
+}
+
+pub enum AppendOutcome {
+    Partial(UploadProgress),
+    /// Every byte arrived and the file is ready for R2
+    Complete(CompletedUpload),
+}
+
+/// An assembled upload waiting to be pushed to R2.
+///
+/// Holds the session lock, so other requests for the session wait until it is
+/// dropped. Call [`Self::finish`] once the push succeeded; dropping it instead
+/// keeps the session and its file, and a PATCH at the final offset retries.
+pub struct CompletedUpload {
+    id: String,
+    session: OwnedMutexGuard<UploadSession>,
+    sessions: Sessions,
+}
+
+impl CompletedUpload {
+    pub fn target(&self) -> &UploadTarget {
+        &self.session.target
+    }
+
+    pub fn file(&self) -> SpooledFile {
+        let temp = self.session.temp.clone().expect("completed upload keeps its file");
+        SpooledFile::from_shared_temp_file(temp, self.session.length)
+    }
+
+    /// Closes the session; the temp file goes away with the last shared reference
+    pub async fn finish(mut self) {
+        // 잠금을 기다리던 다른 요청은 temp가 없으므로 NotFound
+        self.session.temp = None;
+        drop(self.session);
+        self.sessions.write().await.remove(&self.id);
+    }
+}
+
+/// In-progress resumable uploads keyed by session id
+#[derive(Clone)]
+pub struct UploadSessionStore {
+    ttl: Duration,
+    dir: Option<PathBuf>,
+    max_sessions_per_owner: usize,
+    sessions: Sessions,
+}
+
+impl Default for UploadSessionStore {
+    fn default() -> Self {
+        Self::new(UPLOAD_SESSION_TTL)
+    }
+}
+
+impl UploadSessionStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            dir: None,
+            max_sessions_per_owner: DEFAULT_MAX_SESSIONS_PER_ACCOUNT,
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Directory for the partial files; the system temp dir when unset
+    pub fn with_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.dir = dir;
+        self
+    }
+
+    /// How many open sessions one account may hold at once
+    pub fn with_max_sessions_per_owner(mut self, max_sessions_per_owner: usize) -> Self {
+        self.max_sessions_per_owner = max_sessions_per_owner;
+        self
+    }
+
+    pub async fn create(&self, target: UploadTarget, length: u64, owner: &str) -> Result<String, UploadSessionError> {
+        // 개수 확인과 추가 사이에 다른 요청이 끼지 않도록 쓰기 잠금을 먼저 잡음
+        let mut sessions = self.sessions.write().await;
+        let open = sessions
+            .values()
+            .filter(|entry| entry.owner == owner && entry.is_open(self.ttl))
+            .count();
+        if open >= self.max_sessions_per_owner {
+            return Err(UploadSessionError::TooManySessions { limit: self.max_sessions_per_owner });
+        }
+
+        let mut builder = tempfile::Builder::new();
+        builder.prefix(SPILL_FILE_PREFIX);
+        let temp = match &self.dir {
+            Some(dir) => builder.tempfile_in(dir)?,
+            None => builder.tempfile()?,
+        };
+        let id = Uuid::new_v4().to_string();
+        let session = UploadSession {
+            target,
+            length,
+            offset: 0,
+            temp: Some(Arc::new(temp)),
+            last_activity: Instant::now(),
+        };
+        sessions.insert(id.clone(), SessionEntry {
+            owner: owner.to_string(),
+            session: Arc::new(Mutex::new(session)),
+        });
+        Ok(id)
+    }
+
+    /// Drops sessions idle for longer than the TTL, with their temp files.
+    /// Sessions in use are locked and skipped. Returns how many were removed.
+    pub async fn sweep_expired(&self) -> usize {
+        let mut sessions = self.sessions.write().await;
+        let before = sessions.len();
+        sessions.retain(|_, entry| entry.is_open(self.ttl));
+        before - sessions.len()
+    }
+
+    /// Runs [`Self::sweep_expired`] every `interval` in the background
+    pub fn spawn_sweeper(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let removed = self.sweep_expired().await;
+                if removed > 0 {
+                    tracing::info!("Removed {} expired upload session(s)", removed);
+                }
+            }
+        })
+    }
+
+    // 세션 잠금 획득 (만료됐거나 다른 계정의 세션이면 오류)
+    async fn lock_session(&self, id: &str, owner: &str) -> Result<OwnedMutexGuard<UploadSession>, UploadSessionError> {
+        let entry = self
+            .sessions
+            .read()
+            .await
+            .get(id)
+            .cloned()
+            .ok_or_else(|| UploadSessionError::NotFound(id.to_string()))?;
+        let session = entry.session.lock_owned().await;
+        if !session.is_open(self.ttl) {
+            return Err(UploadSessionError::NotFound(id.to_string()));
+        }
+        if entry.owner != owner {
+            return Err(UploadSessionError::NotOwner(id.to_string()));
+        }
+        Ok(session)
+    }
+
+    pub async fn progress(&self, id: &str, owner: &str) -> Result<UploadProgress, UploadSessionError> {
+        let session = self.lock_session(id, owner).await?;
+        Ok(UploadProgress { offset: session.offset, length: session.length })
+    }
+
+    /// Appends `chunks` at `offset`, which must equal the bytes received so far.
+    /// Bytes written before a read error are kept, so the client can resume from
+    /// the offset reported by [`Self::progress`]. Once every byte is in (including
+    /// an empty PATCH at the final offset after a failed push) the upload is
+    /// returned as [`AppendOutcome::Complete`].
+    pub async fn append<S, E>(&self, id: &str, owner: &str, offset: u64, mut chunks: S) -> Result<AppendOutcome, UploadSessionError>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin,
+        E: std::fmt::Display,
+    {
+        // 같은 세션에 대한 PATCH는 하나씩 처리
+        let mut session = self.lock_session(id, owner).await?;
+        let path = match &session.temp {
+            Some(temp) => temp.path().to_path_buf(),
+            None => return Err(UploadSessionError::NotFound(id.to_string())),
+        };
+        if offset != session.offset {
+            return Err(UploadSessionError::OffsetMismatch { expected: session.offset, actual: offset });
+        }
+        session.last_activity = Instant::now();
+
+        let mut file = tokio::fs::OpenOptions::new().append(true).open(&path).await?;
+        let mut result = Ok(());
+        while let Some(chunk) = chunks.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    result = Err(UploadSessionError::Read(e.to_string()));
+                    break;
+                }
+            };
+            if session.offset + chunk.len() as u64 > session.length {
+                result = Err(UploadSessionError::ExceedsLength { length: session.length });
+                break;
+            }
+            if let Err(e) = file.write_all(&chunk).await {
+                result = Err(e.into());
+                break;
+            }
+            session.offset += chunk.len() as u64;
+        }
+        file.flush().await?;
+        result?;
+
+        if session.offset < session.length {
+            return Ok(AppendOutcome::Partial(UploadProgress { offset: session.offset, length: session.length }));
+        }
+        Ok(AppendOutcome::Complete(CompletedUpload {
+            id: id.to_string(),
+            session,
+            sessions: self.sessions.clone(),
+        }))
+    }
+
+    // 임시 파일은 세션을 잡고 있는 마지막 요청이 끝나면 삭제됨
+    pub async fn cancel(&self, id: &str, owner: &str) -> Result<(), UploadSessionError> {
+        drop(self.lock_session(id, owner).await?);
+        self.sessions
+            .write()
+            .await
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| UploadSessionError::NotFound(id.to_string()))
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::Result;
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::models::user::{AdminUser, User};
+
+/// Local admin accounts with Argon2id-hashed passwords, checked before the external login API.
+#[derive(Clone)]
+pub struct UserService {
+    // username -> User
+    users: Arc<RwLock<HashMap<String, User>>>,
+    hash_params: Params,
+    // 없는 계정도 같은 시간 동안 검증하도록 쓰는 해시 (처음 필요할 때 생성)
+    dummy_hash: Arc<OnceLock<String>>,
+}
+
+impl Default for UserService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UserService {
+    pub fn new() -> Self {
+        Self::with_hash_params(Params::default())
+    }
+
+    /// Argon2id cost for new hashes; existing hashes are verified with the params they carry
+    pub fn with_hash_params(hash_params: Params) -> Self {
+        Self {
+            users: Arc::new(RwLock::new(HashMap::new())),
+            hash_params,
+            dummy_hash: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// PHC string (`$argon2id$...`). CPU- and memory-heavy, so async callers go through `spawn_blocking`.
+    pub fn hash_password(&self, password: &str) -> Result<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.hash_params.clone())
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))
+    }
+
+    pub fn verify_password(password: &str, password_hash: &str) -> bool {
+        PasswordHash::new(password_hash)
+            .and_then(|hash| Argon2::default().verify_password(password.as_bytes(), &hash))
+            .is_ok()
+    }
+
+    pub async fn create_user(&self, username: &str, password: &str, role: &str) -> Result<User> {
+        let service = self.clone();
+        let password = password.to_string();
+        let password_hash = tokio::task::spawn_blocking(move || service.hash_password(&password)).await??;
+
+        let mut users = self.users.write().await;
+        if users.contains_key(username) {
+            anyhow::bail!("User already exists: {}", username);
+        }
+
+        let user = User {
+            id: users.len() as i32 + 1,
+            user_id: Uuid::new_v4(),
+            username: username.to_string(),
+            password_hash,
+            role: role.to_string(),
+            is_active: true,
+        };
+        users.insert(username.to_string(), user.clone());
+
+        tracing::info!("Created local user: {} ({})", username, role);
+        Ok(user)
+    }
+
+    // 계정/비밀번호가 일치하는 활성 사용자 반환
+    // (해시 검증은 잠금을 놓은 뒤 블로킹 스레드에서 실행)
+    // 없는/비활성 계정도 더미 해시로 검증해서 응답 시간으로 계정 존재 여부가 드러나지 않게 함
+    pub async fn authenticate(&self, username: &str, password: &str) -> Option<AdminUser> {
+        let user = self.users.read().await.get(username).filter(|user| user.is_active).cloned();
+
+        let service = self.clone();
+        let password = password.to_string();
+        let password_hash = user.as_ref().map(|user| user.password_hash.clone());
+        let verified = tokio::task::spawn_blocking(move || match password_hash {
+            Some(password_hash) => Self::verify_password(&password, &password_hash),
+            None => {
+                Self::verify_password(&password, service.dummy_hash());
+                false
+            }
+        })
+        .await
+        .unwrap_or(false);
+        user.filter(|_| verified).map(AdminUser::from)
+    }
+
+    // 현재 해시 비용으로 만든 고정 해시; 실제 계정과 같은 비용으로 검증됨
+    fn dummy_hash(&self) -> &str {
+        self.dummy_hash.get_or_init(|| self.hash_password("dummy-password-for-timing").unwrap_or_default())
+    }
+
+    /// Creates the initial HEAD_OFFICE admin if no local users exist yet.
+    pub async fn seed_initial_admin(&self, username: &str, password: &str) -> Result<bool> {
+        if !self.users.read().await.is_empty() {
+            return Ok(false);
+        }
+
+        self.create_user(username, password, "HEAD_OFFICE").await?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::Result;
+use futures::future::join_all;
+use reqwest::Client;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::models::asset::YouTubeLink;
+
+pub const DEFAULT_OEMBED_URL: &str = "https://www.youtube.com/oembed";
+
+/// Availability of a YouTube URL as reported by oEmbed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum YouTubeLinkStatus {
+    Valid,
+    /// 404 (removed) or 401 (private / embedding disabled)
+    Unavailable,
+    /// oEmbed could not be reached or answered with an unexpected status
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+struct OEmbedResponse {
+    title: String,
+}
+
+/// YouTube oEmbed lookups used to fill in link titles
+#[derive(Clone)]
+pub struct YouTubeService {
+    client: Client,
+    oembed_url: String,
+    enrich_titles: bool,
+}
+
+impl Default for YouTubeService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl YouTubeService {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            client,
+            oembed_url: DEFAULT_OEMBED_URL.to_string(),
+            enrich_titles: true,
+        }
+    }
+
+    pub fn with_oembed_url(mut self, oembed_url: String) -> Self {
+        self.oembed_url = oembed_url;
+        self
+    }
+
+    pub fn with_title_enrichment(mut self, enabled: bool) -> Self {
+        self.enrich_titles = enabled;
+        self
+    }
+
+    /// Real video title from oEmbed; `None` when the video is unavailable
+    pub async fn fetch_title(&self, youtube_url: &str) -> Result<Option<String>> {
+        let response = self.client
+            .get(&self.oembed_url)
+            .query(&[("url", youtube_url), ("format", "json")])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            debug!("oEmbed returned {} for {}", response.status(), youtube_url);
+            return Ok(None);
+        }
+
+        let oembed: OEmbedResponse = response.json().await?;
+        Ok(Some(oembed.title))
+    }
+
+    pub async fn check_link(&self, youtube_url: &str) -> YouTubeLinkStatus {
+        let response = self.client
+            .get(&self.oembed_url)
+            .query(&[("url", youtube_url), ("format", "json")])
+            .send()
+            .await;
+
+        match response.map(|response| response.status()) {
+            Ok(status) if status.is_success() => YouTubeLinkStatus::Valid,
+            Ok(StatusCode::NOT_FOUND | StatusCode::UNAUTHORIZED) => YouTubeLinkStatus::Unavailable,
+            Ok(status) => {
+                warn!("Unexpected oEmbed status {} for {}", status, youtube_url);
+                YouTubeLinkStatus::Unknown
+            }
+            Err(e) => {
+                warn!("oEmbed check failed for {}: {}", youtube_url, e);
+                YouTubeLinkStatus::Unknown
+            }
+        }
+    }
+
+    /// 제목 없는 링크를 oEmbed 제목으로 채우고, 실패하면 파일명 기반 제목 사용
+    pub async fn process_youtube_links(&self, links: Vec<YouTubeLink>) -> Vec<YouTubeLink> {
+        join_all(links.into_iter().map(|mut link| async move {
+            if link.title.is_none() {
+                let fetched = if self.enrich_titles {
+                    match self.fetch_title(&link.youtube_url).await {
+                        Ok(title) => title,
+                        Err(e) => {
+                            warn!("oEmbed lookup failed for {}: {}", link.youtube_url, e);
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+                link.title = fetched.or_else(|| heuristic_title(&link.thumbnail_file));
+            }
+            link
+        }))
+        .await
+    }
+}
+
+/// `"01_hello-song.png"` -> `"01 hello song"`
+pub fn heuristic_title(thumbnail_file: &str) -> Option<String> {
+    let name = thumbnail_file.rsplit('/').next().unwrap_or(thumbnail_file);
+    let stem = name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(name);
+    let title = stem
+        .split(['_', '-'])
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    (!title.is_empty()).then_some(title)
+}
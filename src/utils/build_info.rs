@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+use crate::config::AppSettings;
+
+/// Which build is running: app name/version from config, git commit and build time from build.rs
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildInfo {
+    pub name: String,
+    pub version: String,
+    pub commit: String,
+    /// RFC 3339; `None` if build.rs could not read the clock
+    pub built_at: Option<String>,
+}
+
+impl BuildInfo {
+    pub fn new(app: &AppSettings) -> Self {
+        let built_at = env!("BUILD_TIMESTAMP")
+            .parse::<i64>()
+            .ok()
+            .filter(|secs| *secs > 0)
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .map(|time| time.to_rfc3339());
+        Self {
+            name: app.name.clone(),
+            version: app.version.clone(),
+            commit: env!("GIT_COMMIT_HASH").to_string(),
+            built_at,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+/// Compares two byte strings in time that depends only on their lengths, not on
+/// where they first differ. Use for passwords, API keys and other credentials.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    // 최적화로 조기 종료되지 않도록
+    std::hint::black_box(diff) == 0
+}
@@ -0,0 +1,54 @@
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::error::ApiError;
+use crate::utils::json_format::JsonFormat;
+
+/// Strong ETag (quoted) derived from the SHA-256 of `body`
+pub fn etag_for(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    let hex: String = digest.iter().take(16).map(|byte| format!("{:02x}", byte)).collect();
+    format!("\"{}\"", hex)
+}
+
+/// Whether the request's `If-None-Match` matches `etag` (handles lists, `*` and `W/` prefixes)
+pub fn if_none_match_matches(headers: &HeaderMap, etag: &str) -> bool {
+    let Some(value) = headers.get(header::IF_NONE_MATCH).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+
+    value.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+/// Serializes `value` as JSON with an `ETag`, or answers 304 when the client already has it
+pub fn json_with_etag<T: Serialize>(request_headers: &HeaderMap, value: &T, format: JsonFormat) -> Response {
+    let body = match format.to_vec(value) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!("Failed to serialize response: {}", e);
+            return ApiError::internal("Failed to serialize response").into_response();
+        }
+    };
+
+    let etag = etag_for(&body);
+    let etag_header = HeaderValue::from_str(&etag).expect("hex ETag is a valid header value");
+
+    if if_none_match_matches(request_headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag_header)]).into_response();
+    }
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, HeaderValue::from_static("application/json")),
+            (header::ETAG, etag_header),
+        ],
+        body,
+    ).into_response()
+}
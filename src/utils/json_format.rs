@@ -0,0 +1,87 @@
+use std::convert::Infallible;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, HeaderValue, StatusCode, Uri},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use crate::error::ApiError;
+
+/// Pretty or compact JSON for a response, chosen per request.
+///
+/// Compact unless the request asks for `?pretty=true` or sends
+/// `Accept: application/json; pretty=true`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonFormat {
+    pub pretty: bool,
+}
+
+impl JsonFormat {
+    pub fn from_request(uri: &Uri, headers: &HeaderMap) -> Self {
+        Self {
+            pretty: pretty_query(uri) || pretty_accept(headers),
+        }
+    }
+
+    pub fn to_vec<T: Serialize>(&self, value: &T) -> serde_json::Result<Vec<u8>> {
+        if self.pretty {
+            serde_json::to_vec_pretty(value)
+        } else {
+            serde_json::to_vec(value)
+        }
+    }
+
+    /// `200 OK` JSON response in this format
+    pub fn json<T: Serialize>(&self, value: &T) -> Response {
+        match self.to_vec(value) {
+            Ok(body) => (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+                body,
+            ).into_response(),
+            Err(e) => {
+                tracing::error!("Failed to serialize response: {}", e);
+                ApiError::internal("Failed to serialize response").into_response()
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for JsonFormat {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_request(&parts.uri, &parts.headers))
+    }
+}
+
+fn is_true(value: &str) -> bool {
+    matches!(value.trim().to_ascii_lowercase().as_str(), "true" | "1" | "yes")
+}
+
+fn pretty_query(uri: &Uri) -> bool {
+    uri.query()
+        .map(|query| {
+            query.split('&').any(|pair| match pair.split_once('=') {
+                Some((key, value)) => key == "pretty" && is_true(value),
+                None => pair == "pretty",
+            })
+        })
+        .unwrap_or(false)
+}
+
+// "application/json; pretty=true" (파라미터 이름은 대소문자 무시)
+fn pretty_accept(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .flat_map(|media_range| media_range.split(';').skip(1))
+        .filter_map(|param| param.split_once('='))
+        .any(|(name, value)| name.trim().eq_ignore_ascii_case("pretty") && is_true(value.trim_matches('"')))
+}
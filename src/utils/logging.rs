@@ -1,19 +1,79 @@
 use anyhow::Ok;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-
-pub fn init_logging() -> Result<(), anyhow::Error> {
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "reengkigo-admin-app=debug".into()),
-        )
-        .with(
-            tracing_subscriber::fmt::layer()
-                .with_target(true)
-                .with_file(true)
-                .with_line_number(true),
-        )
-        .init();
-
-    Ok(())
+use tracing_subscriber::{
+    layer::{Layered, SubscriberExt},
+    reload,
+    util::SubscriberInitExt,
+    EnvFilter, Layer, Registry,
+};
+
+/// Log output format, selected with `LOG_FORMAT` (`json` or `pretty`, default `pretty`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Pretty,
+    Json,
+}
+
+impl LogFormat {
+    pub fn from_env() -> Self {
+        match std::env::var("LOG_FORMAT") {
+            std::result::Result::Ok(value) if value.eq_ignore_ascii_case("json") => LogFormat::Json,
+            _ => LogFormat::Pretty,
+        }
+    }
+}
+
+type FmtLayer = Box<dyn Layer<Registry> + Send + Sync>;
+type FmtSubscriber = Layered<FmtLayer, Registry>;
+
+/// Handle for swapping the active `EnvFilter` at runtime
+pub type LogReloadHandle = reload::Handle<EnvFilter, FmtSubscriber>;
+
+/// Subscriber stack the optional trace-export layer is attached to
+pub type FilteredSubscriber = Layered<reload::Layer<EnvFilter, FmtSubscriber>, FmtSubscriber>;
+pub type TraceLayer = Box<dyn Layer<FilteredSubscriber> + Send + Sync>;
+
+/// Handle for installing the trace-export layer after startup
+pub type TraceReloadHandle = reload::Handle<Option<TraceLayer>, FilteredSubscriber>;
+
+pub struct LoggingHandles {
+    pub log_level: LogReloadHandle,
+    pub trace_layer: TraceReloadHandle,
+}
+
+pub fn init_logging() -> Result<LoggingHandles, anyhow::Error> {
+    let (subscriber, handles) = build_subscriber(LogFormat::from_env());
+    subscriber.init();
+
+    Ok(handles)
+}
+
+pub fn build_subscriber(format: LogFormat) -> (impl tracing::Subscriber + Send + Sync, LoggingHandles) {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| "reengkigo-admin-app=debug".into());
+    let (filter_layer, log_level) = reload::Layer::new(filter);
+    // Empty until ObservabilityManager installs an exporter
+    let (trace_layer, trace_layer_handle) = reload::Layer::new(None::<TraceLayer>);
+
+    let subscriber = tracing_subscriber::registry()
+        .with(fmt_layer(format))
+        .with(filter_layer)
+        .with(trace_layer);
+
+    (subscriber, LoggingHandles { log_level, trace_layer: trace_layer_handle })
+}
+
+fn fmt_layer(format: LogFormat) -> FmtLayer {
+    match format {
+        LogFormat::Pretty => tracing_subscriber::fmt::layer()
+            .with_target(true)
+            .with_file(true)
+            .with_line_number(true)
+            .boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_target(true)
+            .with_file(true)
+            .with_line_number(true)
+            .boxed(),
+    }
 }
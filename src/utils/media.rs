@@ -0,0 +1,26 @@
+//! Image formats accepted for upload and served by the image proxy.
+
+/// Lowercase extension (no dot) and the `Content-Type` it is served with
+pub const IMAGE_FORMATS: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("avif", "image/avif"),
+];
+
+/// Content type for an image extension (case-insensitive, leading dot allowed)
+pub fn image_content_type(extension: &str) -> Option<&'static str> {
+    let extension = extension.trim_start_matches('.');
+    IMAGE_FORMATS
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(extension))
+        .map(|(_, content_type)| *content_type)
+}
+
+/// Content type for a filename's extension, if it is a known image format
+pub fn image_content_type_for(filename: &str) -> Option<&'static str> {
+    let (_, extension) = filename.rsplit_once('.')?;
+    image_content_type(extension)
+}
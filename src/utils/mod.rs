@@ -1,20 +1,100 @@
+pub mod build_info;
+pub mod constant_time;
+pub mod etag;
+pub mod json_format;
 pub mod logging;
+pub mod media;
+pub mod multipart;
+pub mod pagination;
+pub mod path;
+pub mod spool;
+pub mod telemetry;
+
+pub use constant_time::constant_time_eq;
 
 use anyhow::Result;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::trace::TracerProvider;
 use std::sync::Arc;
+use tracing_subscriber::{EnvFilter, Layer};
 
 use crate::config::AppConfig;
+use logging::{LogReloadHandle, LoggingHandles};
 
 pub struct ObservabilityManager {
     config: Arc<AppConfig>,
+    log_reload: Option<LogReloadHandle>,
+    tracer_provider: Option<TracerProvider>,
 }
 
 impl ObservabilityManager {
+    /// Sets up OTLP trace export when `observability.otlp_endpoint` (or
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT`) is set; otherwise tracing stays local.
     pub async fn new(config: Arc<AppConfig>) -> Result<Self> {
-        Ok(Self { config })
+        let endpoint = config.observability.otlp_endpoint.clone()
+            .or_else(|| std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok())
+            .filter(|endpoint| !endpoint.is_empty());
+
+        let tracer_provider = match endpoint {
+            Some(endpoint) => {
+                tracing::info!("Exporting traces to OTLP endpoint: {}", endpoint);
+                Some(telemetry::init_otlp_tracer_provider(&endpoint, &config.observability.service_name)?)
+            }
+            None => None,
+        };
+
+        Ok(Self { config, log_reload: None, tracer_provider })
+    }
+
+    /// Attaches the logging reload handles and, if trace export is enabled,
+    /// installs the OpenTelemetry layer into the running subscriber.
+    pub fn with_logging_handles(mut self, handles: LoggingHandles) -> Self {
+        if let Some(provider) = &self.tracer_provider {
+            let tracer = provider.tracer(self.config.observability.service_name.clone());
+            let layer = tracing_opentelemetry::layer().with_tracer(tracer).boxed();
+            if let Err(e) = handles.trace_layer.reload(Some(layer)) {
+                tracing::error!("Failed to install trace export layer: {}", e);
+            }
+        }
+
+        self.log_reload = Some(handles.log_level);
+        self
     }
 
     pub fn get_config(&self) -> &Arc<AppConfig> {
         &self.config
     }
+
+    pub fn is_trace_export_enabled(&self) -> bool {
+        self.tracer_provider.is_some()
+    }
+
+    /// Flushes pending spans; call before the process exits.
+    pub fn shutdown(&self) {
+        if let Some(provider) = &self.tracer_provider {
+            if let Err(e) = provider.shutdown() {
+                tracing::warn!("Failed to shut down tracer provider: {}", e);
+            }
+        }
+    }
+
+    /// Replaces the active log filter with `directive` (e.g. `server_test=trace`).
+    ///
+    /// The directive is parsed before anything is swapped, so an invalid one
+    /// leaves the current filter in place.
+    pub fn set_log_level(&self, directive: &str) -> Result<()> {
+        let filter = EnvFilter::try_new(directive)
+            .map_err(|e| anyhow::anyhow!("Invalid log directive '{}': {}", directive, e))?;
+
+        let handle = self.log_reload.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Log level reloading is not enabled"))?;
+        handle.reload(filter)?;
+
+        tracing::info!("Log level changed to: {}", directive);
+        Ok(())
+    }
+
+    pub fn current_log_level(&self) -> Option<String> {
+        self.log_reload.as_ref()?.with_current(|filter| filter.to_string()).ok()
+    }
 }
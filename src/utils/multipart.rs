@@ -0,0 +1,11 @@
+/// Error message when an upload form carries no usable file part
+pub const NO_FILE_MESSAGE: &str = "No file provided";
+
+/// Error message when an upload form has a file but no `fullpath`
+pub const MISSING_FULLPATH_MESSAGE: &str = "Missing fullpath parameter";
+
+/// Whether a file part should be treated as absent. A file input left empty is
+/// still submitted as a part with an empty filename and no content.
+pub fn is_blank_file_name(filename: Option<&str>) -> bool {
+    filename.is_none_or(|name| name.trim().is_empty())
+}
@@ -0,0 +1,88 @@
+use axum::http::{HeaderMap, HeaderValue, Uri};
+use serde::Deserialize;
+
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+pub const PAGE_HEADER: &str = "x-page";
+
+// page만 주어졌을 때의 기본 페이지 크기
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+pub const MAX_PAGE_LIMIT: usize = 500;
+
+/// Optional `page`/`limit` query parameters; without either the whole list is one page
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct PageParams {
+    pub page: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+/// One page of a list plus what the pagination headers need
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: usize,
+    pub limit: Option<usize>,
+    pub total: usize,
+}
+
+impl PageParams {
+    pub fn paginate<T>(&self, items: Vec<T>) -> Page<T> {
+        let total = items.len();
+        if self.page.is_none() && self.limit.is_none() {
+            return Page { items, page: 1, limit: None, total };
+        }
+
+        let page = self.page.unwrap_or(1).max(1);
+        let limit = self.limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT);
+        // page는 사용자 입력이므로 곱셈이 넘치지 않게 (넘치면 빈 페이지)
+        let items = items.into_iter().skip((page - 1).saturating_mul(limit)).take(limit).collect();
+        Page { items, page, limit: Some(limit), total }
+    }
+}
+
+impl<T> Page<T> {
+    /// `X-Total-Count`, `X-Page` and, when paginated, a `Link` header with `next`/`prev`
+    /// URLs built from `uri` with only `page` changed
+    pub fn headers(&self, uri: &Uri) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(TOTAL_COUNT_HEADER, HeaderValue::from(self.total));
+        headers.insert(PAGE_HEADER, HeaderValue::from(self.page));
+
+        let Some(limit) = self.limit else {
+            return headers;
+        };
+        let mut links = Vec::new();
+        if self.page.saturating_mul(limit) < self.total {
+            links.push(format!("<{}>; rel=\"next\"", page_uri(uri, self.page + 1)));
+        }
+        if self.page > 1 {
+            links.push(format!("<{}>; rel=\"prev\"", page_uri(uri, self.page - 1)));
+        }
+        if !links.is_empty() {
+            if let Ok(value) = HeaderValue::from_str(&links.join(", ")) {
+                headers.insert(axum::http::header::LINK, value);
+            }
+        }
+        headers
+    }
+}
+
+fn page_uri(uri: &Uri, page: usize) -> String {
+    let mut pairs: Vec<(String, String)> = uri
+        .query()
+        .map(|query| {
+            reqwest::Url::parse(&format!("http://localhost/?{}", query))
+                .map(|url| url.query_pairs().into_owned().collect())
+                .unwrap_or_default()
+        })
+        .unwrap_or_default();
+    pairs.retain(|(key, _)| key != "page");
+    pairs.push(("page".to_string(), page.to_string()));
+
+    let mut url = reqwest::Url::parse("http://localhost/").expect("static URL");
+    url.set_path(uri.path());
+    url.query_pairs_mut().extend_pairs(pairs);
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
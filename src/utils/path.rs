@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreadcrumbItem {
+    pub name: String,
+    pub path: String,
+}
+
+/// Normalizes a folder path: trims leading/trailing slashes and drops empty
+/// segments, so `"/a//b/"` becomes `"a/b"`. The root is the empty string.
+pub fn normalize_path(path: &str) -> String {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Whether `name` can be used as a single folder segment, i.e. contains no
+/// `/` or `\\` that would create nested folders.
+pub fn is_single_segment(name: &str) -> bool {
+    !name.contains(['/', '\\'])
+}
+
+/// Builds breadcrumbs from "Home" down to `path`, each carrying the
+/// cumulative path used for navigation.
+pub fn build_breadcrumbs(path: &str) -> Vec<BreadcrumbItem> {
+    let mut breadcrumbs = vec![
+        BreadcrumbItem {
+            name: "Home".to_string(),
+            path: "".to_string(),
+        }
+    ];
+
+    let normalized = normalize_path(path);
+    let mut current_path = String::new();
+
+    for part in normalized.split('/').filter(|segment| !segment.is_empty()) {
+        if !current_path.is_empty() {
+            current_path.push('/');
+        }
+        current_path.push_str(part);
+
+        breadcrumbs.push(BreadcrumbItem {
+            name: part.to_string(),
+            path: current_path.clone(),
+        });
+    }
+
+    breadcrumbs
+}
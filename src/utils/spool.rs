@@ -0,0 +1,156 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use futures::{Stream, StreamExt};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+
+/// Prefix of spilled upload files, so strays in a shared temp dir are identifiable
+pub const SPILL_FILE_PREFIX: &str = "reengkigo-upload-";
+
+#[derive(Debug, Error)]
+pub enum SpoolError {
+    #[error("file exceeds the {max_bytes} byte limit")]
+    TooLarge { max_bytes: u64 },
+    #[error("failed to read upload: {0}")]
+    Read(String),
+    #[error("failed to write temp file: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug)]
+enum SpooledData {
+    Memory(Bytes),
+    // 임시 파일은 drop 시 삭제됨
+    Disk(NamedTempFile),
+    // 다른 곳(이어 올리기 세션)과 공유하는 임시 파일, 마지막 참조가 사라질 때 삭제됨
+    Shared(Arc<NamedTempFile>),
+}
+
+/// Upload body that stays in memory until it crosses a threshold, then moves to a temp file
+#[derive(Debug)]
+pub struct SpooledFile {
+    data: SpooledData,
+    len: u64,
+}
+
+impl From<Bytes> for SpooledFile {
+    fn from(bytes: Bytes) -> Self {
+        Self {
+            len: bytes.len() as u64,
+            data: SpooledData::Memory(bytes),
+        }
+    }
+}
+
+impl From<Vec<u8>> for SpooledFile {
+    fn from(bytes: Vec<u8>) -> Self {
+        Bytes::from(bytes).into()
+    }
+}
+
+impl SpooledFile {
+    /// Buffers `chunks` in memory, spilling to a temp file in `spill_dir` (or the
+    /// system temp dir) once more than `spill_threshold` bytes have arrived.
+    /// The temp file is owned by the result, so every early return removes it.
+    pub async fn from_stream<S, E>(
+        mut chunks: S,
+        spill_threshold: usize,
+        max_bytes: u64,
+        spill_dir: Option<&Path>,
+    ) -> Result<Self, SpoolError>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin,
+        E: std::fmt::Display,
+    {
+        let mut buffer = Vec::new();
+        let mut spilled: Option<(NamedTempFile, tokio::fs::File)> = None;
+        let mut len = 0u64;
+
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk.map_err(|e| SpoolError::Read(e.to_string()))?;
+            len += chunk.len() as u64;
+            if len > max_bytes {
+                return Err(SpoolError::TooLarge { max_bytes });
+            }
+
+            if spilled.is_none() && buffer.len() + chunk.len() > spill_threshold {
+                let mut builder = tempfile::Builder::new();
+                builder.prefix(SPILL_FILE_PREFIX);
+                let temp = match spill_dir {
+                    Some(dir) => builder.tempfile_in(dir)?,
+                    None => builder.tempfile()?,
+                };
+                // 경로로 다시 열지 않고 임시 파일 자신의 핸들로 기록
+                let mut file = tokio::fs::File::from_std(temp.as_file().try_clone()?);
+                file.write_all(&buffer).await?;
+                buffer = Vec::new();
+                spilled = Some((temp, file));
+            }
+
+            match &mut spilled {
+                Some((_, file)) => file.write_all(&chunk).await?,
+                None => buffer.extend_from_slice(&chunk),
+            }
+        }
+
+        let data = match spilled {
+            Some((temp, mut file)) => {
+                file.flush().await?;
+                SpooledData::Disk(temp)
+            }
+            None => SpooledData::Memory(buffer.into()),
+        };
+        Ok(Self { data, len })
+    }
+
+    /// Wraps a temp file already holding `len` bytes that the caller keeps a
+    /// reference to (e.g. an assembled resumable upload, kept for a retry)
+    pub fn from_shared_temp_file(temp: Arc<NamedTempFile>, len: u64) -> Self {
+        Self { data: SpooledData::Shared(temp), len }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Temp file path when the data was spilled to disk
+    pub fn path(&self) -> Option<PathBuf> {
+        match &self.data {
+            SpooledData::Memory(_) => None,
+            SpooledData::Disk(temp) => Some(temp.path().to_path_buf()),
+            SpooledData::Shared(temp) => Some(temp.path().to_path_buf()),
+        }
+    }
+
+    pub fn is_spilled(&self) -> bool {
+        matches!(self.data, SpooledData::Disk(_) | SpooledData::Shared(_))
+    }
+
+    /// Multipart part for the upload API; spilled files are streamed from disk.
+    /// The temp file is removed once the returned part has been sent or dropped
+    /// (for a shared file, once the other references are gone as well).
+    pub async fn into_part(self, filename: String) -> std::io::Result<reqwest::multipart::Part> {
+        let part = match self.data {
+            SpooledData::Memory(bytes) => reqwest::multipart::Part::bytes(bytes.to_vec()),
+            SpooledData::Disk(temp) => stream_part(temp.reopen()?, temp, self.len),
+            SpooledData::Shared(temp) => stream_part(temp.reopen()?, temp, self.len),
+        };
+        Ok(part.file_name(filename))
+    }
+}
+
+// 스트림이 끝날 때까지 임시 파일 핸들을 유지
+fn stream_part<T: Send + Sync + 'static>(file: std::fs::File, temp: T, len: u64) -> reqwest::multipart::Part {
+    let stream = tokio_util::io::ReaderStream::new(tokio::fs::File::from_std(file)).map(move |chunk| {
+        let _keep_alive = &temp;
+        chunk
+    });
+    reqwest::multipart::Part::stream_with_length(reqwest::Body::wrap_stream(stream), len)
+}
@@ -1,4 +1,174 @@
 // Common test utilities
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use axum::Router;
+use server_test::utils::build_info::BuildInfo;
+use server_test::{create_router, AppConfig, AppState, AssetService, AuditService, AuthService, DashboardService, FileService, IdempotencyStore, ObservabilityManager, ProjectListCache, UploadSessionStore, UserService, YouTubeService};
+
+// Unroutable R2 endpoint so tests never reach the real storage API
+pub const TEST_R2_BASE_URL: &str = "http://127.0.0.1:9";
+
 pub fn setup() {
     // Setup code for tests
-}
\ No newline at end of file
+}
+
+/// Minimal Argon2 cost so tests that hash passwords stay fast
+pub fn fast_hash_params() -> argon2::Params {
+    argon2::Params::new(argon2::Params::MIN_M_COST, 1, 1, None).unwrap()
+}
+
+pub async fn test_state() -> AppState {
+    let config = Arc::new(AppConfig::default());
+    let observability = Arc::new(ObservabilityManager::new(config.clone()).await.unwrap());
+    let file_service = Arc::new(FileService::new(
+        TEST_R2_BASE_URL.to_string(),
+        config.external_api.bucket.clone(),
+    ).with_upload_url(format!("{}/upload", TEST_R2_BASE_URL))
+        .with_folder_files_url(format!("{}/folder-files", TEST_R2_BASE_URL)));
+
+    let user_service = Arc::new(UserService::with_hash_params(fast_hash_params()));
+    let audit_service = Arc::new(AuditService::new());
+    let asset_service = Arc::new(AssetService::new());
+    let started_at = std::time::Instant::now();
+    let ready = Arc::new(AtomicBool::new(true));
+    let dashboard_service = Arc::new(DashboardService::new(
+        asset_service.clone(),
+        audit_service.clone(),
+        ready.clone(),
+        started_at,
+        BuildInfo::new(&config.app),
+    ));
+
+    AppState {
+        file_service,
+        audit_service,
+        asset_service,
+        dashboard_service,
+        idempotency_store: Arc::new(IdempotencyStore::default()),
+        upload_sessions: Arc::new(UploadSessionStore::default()),
+        user_service: user_service.clone(),
+        auth_service: Arc::new(AuthService::from_config(&config.auth).with_user_service(user_service)),
+        project_list: Arc::new(ProjectListCache::new("project_list.yaml")),
+        // Enrichment off so tests never call the real oEmbed endpoint
+        youtube_service: Arc::new(YouTubeService::new().with_title_enrichment(false)),
+        config,
+        observability,
+        ready,
+        started_at,
+    }
+}
+
+pub async fn test_app() -> Router {
+    create_router(test_state().await)
+}
+
+/// Serves `router` on an ephemeral local port and returns its base URL.
+pub async fn spawn_mock_server(router: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    format!("http://{}", addr)
+}
+
+/// Files held by [`spawn_r2_store_server`], keyed by full R2 path
+pub type R2Store = Arc<tokio::sync::Mutex<std::collections::HashMap<String, Vec<u8>>>>;
+
+/// Mock R2 API whose `/download/*path` and `/upload` share `store`, so
+/// uploaded files can be read back
+pub async fn spawn_r2_store_server(store: R2Store) -> String {
+    use axum::{
+        extract::{Multipart, Path, State},
+        http::StatusCode,
+        response::IntoResponse,
+        routing::{get, post},
+        Json,
+    };
+
+    let router = Router::new()
+        .route("/download/*path", get(|State(store): State<R2Store>, Path(path): Path<String>| async move {
+            match store.lock().await.get(&path) {
+                Some(bytes) => bytes.clone().into_response(),
+                None => StatusCode::NOT_FOUND.into_response(),
+            }
+        }))
+        .route("/upload", post(|State(store): State<R2Store>, mut multipart: Multipart| async move {
+            let mut fullpath = String::new();
+            let mut uploaded = Vec::new();
+            while let Some(field) = multipart.next_field().await.unwrap() {
+                match field.name().unwrap_or("") {
+                    "fullpath" => fullpath = field.text().await.unwrap(),
+                    "file" => {
+                        let original_file = field.file_name().unwrap_or("").to_string();
+                        let bytes = field.bytes().await.unwrap();
+                        uploaded.push(serde_json::json!({
+                            "file": fullpath, "original_file": original_file, "size": bytes.len(), "subtitle": []
+                        }));
+                        store.lock().await.insert(fullpath.clone(), bytes.to_vec());
+                    }
+                    _ => {}
+                }
+            }
+            Json(serde_json::json!({ "uploaded": uploaded }))
+        }))
+        .with_state(store);
+    spawn_mock_server(router).await
+}
+
+/// Mock R2 upload API that echoes each uploaded part back with its real size.
+pub async fn spawn_echo_upload_server() -> String {
+    spawn_gated_upload_server(Arc::new(tokio::sync::Semaphore::new(1)), Arc::new(tokio::sync::Notify::new())).await
+}
+
+/// Echo upload server that signals `arrived` and then waits for a `gate` permit
+/// before answering, so a test can hold an upload in flight
+pub async fn spawn_gated_upload_server(gate: Arc<tokio::sync::Semaphore>, arrived: Arc<tokio::sync::Notify>) -> String {
+    use axum::{
+        extract::{DefaultBodyLimit, Multipart},
+        routing::post,
+        Json,
+    };
+
+    let router = Router::new().route("/upload", post(move |mut multipart: Multipart| async move {
+        arrived.notify_one();
+        let _permit = gate.acquire().await.unwrap();
+        let mut fullpath = String::new();
+        let mut uploaded = Vec::new();
+        while let Some(field) = multipart.next_field().await.unwrap() {
+            match field.name().unwrap_or("") {
+                "fullpath" => fullpath = field.text().await.unwrap(),
+                "file" => {
+                    let original_file = field.file_name().unwrap_or("").to_string();
+                    let size = field.bytes().await.unwrap().len();
+                    uploaded.push(serde_json::json!({
+                        "file": fullpath, "original_file": original_file, "size": size, "subtitle": []
+                    }));
+                }
+                _ => {}
+            }
+        }
+        Json(serde_json::json!({ "uploaded": uploaded }))
+    }))
+    .layer(DefaultBodyLimit::disable());
+    spawn_mock_server(router).await
+}
+
+pub fn admin_token() -> String {
+    token_for("admin", "HEAD_OFFICE")
+}
+
+pub fn token_for(account: &str, role: &str) -> String {
+    use server_test::{models::user::AdminUser, services::auth::AuthService};
+
+    let user = AdminUser {
+        account_id: 1,
+        account: account.to_string(),
+        role: role.to_string(),
+        agency_id: 1,
+        academy_id: 1,
+        is_active: true,
+    };
+    AuthService::new().generate_admin_token(&user).unwrap()
+}